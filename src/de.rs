@@ -1,23 +1,81 @@
 use std::io::Read;
 
+pub mod brute_force;
 pub mod fmt;
 pub mod fs;
 pub mod magical;
+pub mod sniff;
 
-pub fn make_deserializer<'r, R: Read + 'r, F: TryInto<crate::DeserializationFormat>>(
+/// Makes a deserializer of the format, which may have a compression layer, e.g. `"CBOR+zstd"`.
+pub fn make_deserializer<'r, R, F>(
     reader: R,
     format: F,
-) -> Result<crate::MagicalDeserializer<'r>, F::Error> {
-    format.try_into().map(|fmt| fmt.deserializer(reader))
+) -> color_eyre::Result<crate::MagicalDeserializer<'r>>
+where
+    R: Read + 'r,
+    F: TryInto<crate::Compound<crate::DeserializationFormat>>,
+    color_eyre::Report: From<F::Error>,
+{
+    let crate::Compound {
+        format,
+        compression,
+    } = format.try_into()?;
+    Ok(match compression {
+        Some(compression) => format.deserializer(compression.decoder(reader)?),
+        None => format.deserializer(reader),
+    })
 }
 
 pub fn deserialize_magically<'r, R, F, O>(reader: R, format: F) -> color_eyre::Result<O>
 where
     R: Read + 'r,
-    F: TryInto<crate::DeserializationFormat>,
+    F: TryInto<crate::Compound<crate::DeserializationFormat>>,
     color_eyre::Report: From<F::Error>,
     O: serde::de::DeserializeOwned,
 {
     let mut deserializer: crate::MagicalDeserializer<'r> = make_deserializer(reader, format)?;
     Ok(deserializer.deserialize()?)
 }
+
+/// Deserializes an object, guessing the format from the first bytes of the input.
+///
+/// If the whole input is peeked at, every guessed format is tried from the most likely one.
+/// Otherwise, the peeked bytes are still read by the deserializer of the only guessed format,
+/// and more guesses are an error, as a wrong one may read the input as some other value.
+/// See [`crate::DeserializationFormat::sniff`].
+pub fn deserialize_sniffed<'r, R, O>(reader: R) -> color_eyre::Result<O>
+where
+    R: Read + 'r,
+    O: serde::de::DeserializeOwned,
+{
+    let reader = sniff::peek(reader)?;
+    let guesses = crate::DeserializationFormat::sniff(reader.peeked());
+    let guessed = || {
+        let guesses = guesses.iter().map(ToString::to_string).collect::<Vec<_>>();
+        guesses.join(", ")
+    };
+    match guesses.as_slice() {
+        [] => color_eyre::eyre::bail!("Failed to guess the format of the input."),
+        &[fmt] => deserialize_magically(reader, fmt),
+        _ if reader.is_whole() => {
+            let mut first_error = None;
+            for &fmt in &guesses {
+                match deserialize_magically(reader.peeked(), fmt) {
+                    Ok(deserialized) => return Ok(deserialized),
+                    Err(error) => {
+                        first_error
+                            .get_or_insert(error.wrap_err(format!("Failed to read it as {fmt}.")));
+                    }
+                }
+            }
+            Err(first_error
+                .expect("more formats should have been guessed")
+                .wrap_err(format!("The input reads as none of {}.", guessed())))
+        }
+        _ => color_eyre::eyre::bail!(
+            "The input could be any of {}, which can't all be tried, \
+            as it's longer than the peeked bytes.",
+            guessed()
+        ),
+    }
+}
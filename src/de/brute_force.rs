@@ -0,0 +1,90 @@
+//! Deserializing an input in every enabled format, e.g. to find out what an unknown blob holds.
+
+use color_eyre::eyre::eyre;
+use strum::VariantArray as _;
+
+use crate::DeserializationFormat;
+
+/// The outcome of deserializing the same input in every enabled format.
+#[derive(Debug)]
+pub struct Report<O> {
+    /// Formats in which the input deserialized, with the deserialized objects.
+    pub successes: Vec<(DeserializationFormat, O)>,
+    /// Formats in which the input failed to deserialize, with the errors.
+    pub failures: Vec<(DeserializationFormat, color_eyre::Report)>,
+}
+
+impl<O> Report<O> {
+    pub fn succeeded_formats(&self) -> impl Iterator<Item = DeserializationFormat> + '_ {
+        self.successes.iter().map(|(fmt, _)| *fmt)
+    }
+
+    pub fn failed_formats(&self) -> impl Iterator<Item = DeserializationFormat> + '_ {
+        self.failures.iter().map(|(fmt, _)| *fmt)
+    }
+}
+
+/// Deserializes an object from the bytes in every format of
+/// [`DeserializationFormat::VARIANTS`], reporting what succeeded and what failed.
+///
+/// # Examples
+///
+/// ```rust
+/// #[cfg(all(feature = "json", feature = "yaml"))]
+/// {
+///     let report = sedes::de::brute_force::deserialize_every_format::<Vec<i32>>(b"[1, 2, 42]");
+///     let succeeded: Vec<_> = report.succeeded_formats().collect();
+///     assert!(succeeded.contains(&sedes::DeserializationFormat::Json));
+///     assert!(succeeded.contains(&sedes::DeserializationFormat::Yaml));
+/// }
+/// ```
+pub fn deserialize_every_format<O>(bytes: &[u8]) -> Report<O>
+where
+    O: serde::de::DeserializeOwned,
+{
+    let mut report = Report {
+        successes: Vec::new(),
+        failures: Vec::new(),
+    };
+    for &fmt in DeserializationFormat::VARIANTS {
+        match deserialize(bytes, fmt) {
+            Ok(deserialized) => report.successes.push((fmt, deserialized)),
+            Err(error) => report.failures.push((fmt, error)),
+        }
+    }
+    report
+}
+
+/// Some deserializers panic on arbitrary input (e.g. on huge lengths), which counts as a failure.
+fn deserialize<O>(bytes: &[u8], fmt: DeserializationFormat) -> color_eyre::Result<O>
+where
+    O: serde::de::DeserializeOwned,
+{
+    std::panic::catch_unwind(|| deserialize_unguarded(bytes, fmt)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("no message");
+        Err(eyre!("The {fmt} deserializer panicked: {message}"))
+    })
+}
+
+fn deserialize_unguarded<O>(bytes: &[u8], fmt: DeserializationFormat) -> color_eyre::Result<O>
+where
+    O: serde::de::DeserializeOwned,
+{
+    // Bincode trusts length prefixes, so would allocate whatever garbage tells, but for a limit.
+    #[cfg(feature = "bincode")]
+    if fmt.is_bincode() {
+        use bincode::Options as _;
+        let options = bincode::DefaultOptions::new().with_limit(bytes.len() as u64);
+        let mut deserializer =
+            crate::MagicalDeserializer::new(bincode::Deserializer::with_reader(bytes, options));
+        return Ok(deserializer.deserialize()?);
+    }
+    let mut deserializer = crate::make_deserializer(bytes, fmt)?;
+    Ok(deserializer.deserialize()?)
+}
+#[cfg(test)]
+mod test;
@@ -0,0 +1,31 @@
+use strum::VariantArray as _;
+
+use crate::DeserializationFormat as DF;
+
+#[cfg(all(feature = "json", feature = "yaml", feature = "cbor"))]
+#[test]
+fn reports_every_format() {
+    let report = super::deserialize_every_format::<Vec<i32>>(b"[1, 2, 42]");
+    assert_eq!(
+        report.successes.len() + report.failures.len(),
+        DF::VARIANTS.len()
+    );
+    assert!(report.successes.contains(&(DF::Json, vec![1, 2, 42])));
+    assert!(report.succeeded_formats().any(|fmt| fmt == DF::Yaml));
+    assert!(report.failed_formats().any(|fmt| fmt == DF::Cbor));
+}
+
+#[test]
+fn survives_arbitrary_bytes() {
+    use rand::Rng;
+    let mut rng = rand::rng();
+    for _ in 0..100 {
+        let len = rng.random_range(0..64);
+        let bytes: Vec<u8> = (0..len).map(|_| rng.random()).collect();
+        let report = super::deserialize_every_format::<Vec<String>>(&bytes);
+        assert_eq!(
+            report.successes.len() + report.failures.len(),
+            DF::VARIANTS.len()
+        );
+    }
+}
@@ -35,6 +35,10 @@ pub enum DeserializationFormat {
     #[default]
     Json,
 
+    /// YAML, possibly as a stream of `---`-separated documents.
+    ///
+    /// Every document is a separate value, so [`crate::MagicalDeserializer::deserialize`]
+    /// reads just the first one, while [`crate::MagicalDeserializer::into_values`] reads all.
     #[cfg(feature = "yaml")]
     #[strum(serialize = "YAML", props(file_ext = "yml", alt_file_exts = "yaml"))]
     Yaml,
@@ -54,11 +58,208 @@ pub enum DeserializationFormat {
     #[cfg(feature = "pickle")]
     #[strum(serialize = "Pickle", props(file_ext = "pkl"))]
     Pickle,
+
+    #[cfg(feature = "toml")]
+    #[strum(serialize = "TOML", props(file_ext = "toml"))]
+    Toml,
+
+    #[cfg(feature = "ron")]
+    #[strum(serialize = "RON", props(file_ext = "ron"))]
+    Ron,
+
+    /// JSON5, i.e. JSON extended with comments, trailing commas, unquoted keys,
+    /// single-quoted strings, hexadecimal numbers, etc.
+    ///
+    /// Serialized back as strict JSON.
+    #[cfg(feature = "json5")]
+    #[strum(serialize = "JSON5", props(file_ext = "json5"))]
+    Json5,
+
+    /// JSON with comments and trailing commas, as in VS Code settings files.
+    ///
+    /// Serialized back as strict JSON.
+    #[cfg(feature = "jsonc")]
+    #[strum(serialize = "JSONC", props(file_ext = "jsonc"))]
+    Jsonc,
+
+    /// XML, whose root element name is ignored while deserializing.
+    #[cfg(feature = "xml")]
+    #[strum(serialize = "XML", props(file_ext = "xml"))]
+    Xml,
+
+    /// Comma-separated values with a header, holding a sequence of flat records.
+    #[cfg(feature = "csv")]
+    #[strum(serialize = "CSV", props(file_ext = "csv"))]
+    Csv,
+
+    /// Tab-separated values with a header, holding a sequence of flat records.
+    #[cfg(feature = "csv")]
+    #[strum(serialize = "TSV", props(file_ext = "tsv"))]
+    Tsv,
+
+    /// BSON, which holds a document (i.e. a struct or a map) at the top level.
+    #[cfg(feature = "bson")]
+    #[strum(serialize = "BSON", props(file_ext = "bson"))]
+    Bson,
+
+    #[cfg(feature = "postcard")]
+    #[strum(serialize = "Postcard", props(file_ext = "postcard"))]
+    Postcard,
+
+    /// Postcard framed with Consistent Overhead Byte Stuffing, ending with a zero byte.
+    #[cfg(feature = "postcard")]
+    #[strum(serialize = "Postcard-COBS", props(file_ext = "cobs"))]
+    PostcardCobs,
+
+    #[cfg(feature = "flexbuffers")]
+    #[strum(
+        serialize = "FlexBuffers",
+        props(file_ext = "flexbuf", alt_file_exts = "flexbuffers")
+    )]
+    Flexbuffers,
+
+    /// S-expressions in the Scheme-like dialect, with `#t`/`#f` booleans and `#:keywords`.
+    ///
+    /// Data written as [`SerializationFormat::ElispSexpr`] is read in this dialect too,
+    /// so its `t`/`nil` are symbols rather than booleans.
+    #[cfg(feature = "sexpr")]
+    #[strum(
+        serialize = "S-expression",
+        serialize = "Lisp",
+        props(file_ext = "sexp")
+    )]
+    Sexpr,
+
+    /// `application/x-www-form-urlencoded` form bodies and query strings.
+    #[cfg(feature = "urlencoded")]
+    #[strum(
+        serialize = "URL-encoded",
+        serialize = "urlencoded",
+        props(file_ext = "urlencoded")
+    )]
+    UrlEncoded,
+
+    /// JSON Lines (a.k.a. NDJSON), i.e. a stream of JSON documents, one per line.
+    ///
+    /// Holds many values, so it's best read with [`crate::MagicalDeserializer::into_values`].
+    #[cfg(feature = "jsonl")]
+    #[strum(
+        serialize = "JSON-Lines",
+        serialize = "NDJSON",
+        props(file_ext = "jsonl", alt_file_exts = "ndjson")
+    )]
+    JsonLines,
+
+    /// Bencode, as in BitTorrent metadata, which has no floats and encodes booleans as integers.
+    #[cfg(feature = "bencode")]
+    #[strum(
+        serialize = "Bencode",
+        props(file_ext = "bencode", alt_file_exts = "torrent")
+    )]
+    Bencode,
+
+    /// Apple property list, in either the XML or the binary encoding, detected automatically.
+    #[cfg(feature = "plist")]
+    #[strum(serialize = "Plist", props(file_ext = "plist"))]
+    Plist,
+
+    /// HOCON, i.e. the JSON superset used for configs of JVM services.
+    ///
+    /// Read-only, as there's no serializer for it.
+    #[cfg(feature = "hocon")]
+    #[strum(serialize = "HOCON", props(file_ext = "conf", alt_file_exts = "hocon"))]
+    Hocon,
+
+    /// INI, whose sections are read as nested structs or maps, with the keys before the first
+    /// section at the top level.
+    ///
+    /// Sections can't nest, so structs and maps deeper than that can't be represented,
+    /// and neither can sequences, nor enum variants other than unit ones. Serializing them fails.
+    /// An empty value stands for `None`. Backslashes aren't escapes and values can't span lines,
+    /// as in Python's `configparser`. Keys and values are trimmed and quotes around values are
+    /// removed, so serializing what would read back differently (e.g. a key holding `=`) fails.
+    #[cfg(feature = "ini")]
+    #[strum(serialize = "INI", props(file_ext = "ini", alt_file_exts = "cfg"))]
+    Ini,
+
+    /// Java properties, whose keys are split at dots into the keys of nested structs or maps,
+    /// e.g. `server.port=8080`.
+    ///
+    /// Sequences and enum variants other than unit ones can't be represented.
+    #[cfg(feature = "properties")]
+    #[strum(
+        serialize = "Java-properties",
+        serialize = "properties",
+        props(file_ext = "properties")
+    )]
+    Properties,
+
+    /// Dotenv variables, whose names are split at underscores into the fields of nested structs,
+    /// e.g. `SERVER_PORT=8080`. Fields match names regardless of their case.
+    ///
+    /// Maps don't nest, as variable names themselves often hold underscores.
+    /// Sequences and enum variants other than unit ones can't be represented.
+    #[cfg(feature = "dotenv")]
+    #[strum(serialize = "dotenv", serialize = ".env", props(file_ext = "env"))]
+    DotEnv,
+
+    /// The front matter opening a Markdown file, i.e. YAML between `---` lines
+    /// or TOML between `+++` lines.
+    ///
+    /// The Markdown body is preserved when writing front matter with [`crate::serialize_to_file`]
+    /// or [`SerializationFormat::markdown_serializer`].
+    #[cfg(feature = "front-matter")]
+    #[strum(
+        serialize = "front-matter",
+        serialize = "Markdown",
+        props(file_ext = "md", alt_file_exts = "markdown")
+    )]
+    FrontMatter,
+
+    /// Amazon Ion, in either the text or the binary encoding, detected automatically.
+    #[cfg(feature = "ion")]
+    #[strum(serialize = "Ion", props(file_ext = "ion", alt_file_exts = "10n"))]
+    Ion,
+
+    /// Apache Avro object container file, whose header tells the writer schema.
+    /// See [`DeserializationFormat::deserializer_with_schema`] to resolve records to a reader schema.
+    #[cfg(feature = "avro")]
+    #[strum(serialize = "Avro", props(file_ext = "avro"))]
+    Avro,
 }
 
 impl DeserializationFormat {
-    pub fn serializer<'w, W: Write + 'w>(self, writer: W) -> crate::MagicalSerializer<'w> {
-        SerializationFormat::from(self).serializer(writer)
+    /// Fails for read-only formats (e.g. HOCON), which have no serializer.
+    pub fn serializer<'w, W: Write + 'w>(
+        self,
+        writer: W,
+    ) -> color_eyre::Result<crate::MagicalSerializer<'w>> {
+        Ok(SerializationFormat::try_from(self)?.serializer(writer))
+    }
+
+    /// Guesses the format from the first bytes of an input (e.g. [`crate::de::sniff::PEEK_LEN`]
+    /// of them), from the most to the least likely. Empty if nothing is recognized.
+    pub fn sniff(bytes: &[u8]) -> Vec<Self> {
+        crate::de::sniff::sniff(bytes)
+    }
+
+    /// Makes a deserializer given a `schema`, e.g. the reader schema of Avro, in its JSON form.
+    ///
+    /// Fails for formats taking no schema, and for invalid schemas.
+    pub fn deserializer_with_schema<'r, R: Read + 'r>(
+        self,
+        reader: R,
+        schema: &str,
+    ) -> color_eyre::Result<crate::MagicalDeserializer<'r>> {
+        #[allow(clippy::match_single_binding)]
+        match self {
+            #[cfg(feature = "avro")]
+            Self::Avro => Ok(avro::deserializer(
+                reader,
+                Some(apache_avro::Schema::parse_str(schema)?),
+            )),
+            _ => color_eyre::eyre::bail!("{self} takes no schema"),
+        }
     }
 
     pub fn deserializer<'r, R: Read + 'r>(self, reader: R) -> crate::MagicalDeserializer<'r> {
@@ -69,7 +270,7 @@ impl DeserializationFormat {
             }
 
             #[cfg(feature = "yaml")]
-            Self::Yaml => crate::MagicalDeserializer::from_direct_impl(
+            Self::Yaml => crate::MagicalDeserializer::from_deserializers(
                 serde_yaml::Deserializer::from_reader(reader),
             ),
 
@@ -92,6 +293,226 @@ impl DeserializationFormat {
                 reader,
                 serde_pickle::DeOptions::default(),
             )),
+
+            #[cfg(feature = "toml")]
+            Self::Toml => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let document = crate::util::read_document(reader)?;
+                let deserializer =
+                    toml::Deserializer::parse(&document).map_err(serde::de::Error::custom)?;
+                consume(&mut <dyn erased_serde::Deserializer>::erase(deserializer))
+            }),
+
+            #[cfg(feature = "ron")]
+            Self::Ron => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let document = crate::util::read_document(reader)?;
+                let mut deserializer =
+                    ron::Deserializer::from_str(&document).map_err(serde::de::Error::custom)?;
+                consume(&mut <dyn erased_serde::Deserializer>::erase(
+                    &mut deserializer,
+                ))?;
+                deserializer.end().map_err(serde::de::Error::custom)
+            }),
+
+            #[cfg(feature = "json5")]
+            Self::Json5 => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let document = crate::util::read_document(reader)?;
+                crate::util::consume_via_typed_api(consume, || json_five::from_str(&document))
+            }),
+
+            #[cfg(feature = "jsonc")]
+            Self::Jsonc => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let document = crate::util::read_document(reader)?;
+                let parse_options = jsonc_parser::ParseOptions {
+                    allow_comments: true,
+                    allow_trailing_commas: true,
+                    allow_loose_object_property_names: false,
+                };
+                let value = jsonc_parser::parse_to_serde_value(&document, &parse_options)
+                    .map_err(serde::de::Error::custom)?
+                    .ok_or_else(|| serde::de::Error::custom("the document holds no value"))?;
+                consume(&mut <dyn erased_serde::Deserializer>::erase(value))
+            }),
+
+            #[cfg(feature = "xml")]
+            Self::Xml => crate::MagicalDeserializer::new(xml::Items(
+                quick_xml::de::Deserializer::from_reader(std::io::BufReader::new(reader)),
+            )),
+
+            #[cfg(feature = "csv")]
+            Self::Csv => {
+                crate::MagicalDeserializer::new(records::RecordsDeserializer::new(reader, b','))
+            }
+            #[cfg(feature = "csv")]
+            Self::Tsv => {
+                crate::MagicalDeserializer::new(records::RecordsDeserializer::new(reader, b'\t'))
+            }
+
+            #[cfg(feature = "bson")]
+            Self::Bson => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let document =
+                    bson::Document::from_reader(reader).map_err(serde::de::Error::custom)?;
+                let deserializer = bson::Deserializer::new(bson::Bson::Document(document));
+                consume(&mut <dyn erased_serde::Deserializer>::erase(deserializer))
+            }),
+
+            #[cfg(feature = "postcard")]
+            Self::Postcard => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let bytes = crate::util::read_bytes(reader)?;
+                let mut deserializer = postcard::Deserializer::from_bytes(&bytes);
+                consume(&mut <dyn erased_serde::Deserializer>::erase(
+                    &mut deserializer,
+                ))
+            }),
+            #[cfg(feature = "postcard")]
+            Self::PostcardCobs => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let mut bytes = crate::util::read_bytes(reader)?;
+                let decoded_len =
+                    cobs::decode_in_place(&mut bytes).map_err(serde::de::Error::custom)?;
+                let mut deserializer = postcard::Deserializer::from_bytes(&bytes[..decoded_len]);
+                consume(&mut <dyn erased_serde::Deserializer>::erase(
+                    &mut deserializer,
+                ))
+            }),
+
+            #[cfg(feature = "flexbuffers")]
+            Self::Flexbuffers => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let bytes = crate::util::read_bytes(reader)?;
+                let root = flexbuffers::Reader::get_root(bytes.as_slice())
+                    .map_err(serde::de::Error::custom)?;
+                consume(&mut <dyn erased_serde::Deserializer>::erase(root))
+            }),
+
+            #[cfg(feature = "sexpr")]
+            Self::Sexpr => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let value =
+                    serde_lexpr::parse::from_reader(reader).map_err(serde::de::Error::custom)?;
+                crate::util::consume_via_typed_api(consume, || serde_lexpr::from_value(&value))
+            }),
+
+            #[cfg(feature = "urlencoded")]
+            Self::UrlEncoded => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let form = crate::util::read_bytes(reader)?;
+                let deserializer =
+                    serde_urlencoded::Deserializer::new(form_urlencoded::parse(&form));
+                consume(&mut <dyn erased_serde::Deserializer>::erase(deserializer)).map_err(|e| {
+                    serde::de::Error::custom(format!(
+                        "{e} ({})",
+                        crate::util::URL_ENCODED_NESTING_LIMITS
+                    ))
+                })
+            }),
+
+            #[cfg(feature = "jsonl")]
+            Self::JsonLines => crate::MagicalDeserializer::from_fn(
+                std::io::BufReader::new(reader),
+                |reader, consume| {
+                    use std::io::BufRead as _;
+                    let mut line = String::new();
+                    while line.trim().is_empty() {
+                        line.clear();
+                        if reader
+                            .read_line(&mut line)
+                            .map_err(serde::de::Error::custom)?
+                            == 0
+                        {
+                            // No more values.
+                            return Ok(());
+                        }
+                    }
+                    let mut deserializer = serde_json::Deserializer::from_str(&line);
+                    consume(&mut <dyn erased_serde::Deserializer>::erase(
+                        &mut deserializer,
+                    ))?;
+                    deserializer.end().map_err(serde::de::Error::custom)
+                },
+            ),
+
+            #[cfg(feature = "bencode")]
+            Self::Bencode => {
+                crate::MagicalDeserializer::new(serde_bencode::Deserializer::new(reader))
+            }
+
+            #[cfg(feature = "plist")]
+            Self::Plist => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let bytes = crate::util::read_bytes(reader)?;
+                crate::util::consume_via_typed_api(consume, || plist::from_bytes(&bytes))
+            }),
+
+            #[cfg(feature = "hocon")]
+            Self::Hocon => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let document = crate::util::read_document(reader)?;
+                crate::util::consume_via_typed_api(consume, || hocon::from_str(&document))
+            }),
+
+            #[cfg(feature = "ini")]
+            Self::Ini => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let ini =
+                    ::ini::Ini::read_from_noescape(reader).map_err(serde::de::Error::custom)?;
+                consume(&mut <dyn erased_serde::Deserializer>::erase(
+                    ini::deserializer(&ini),
+                ))
+            }),
+
+            #[cfg(feature = "properties")]
+            Self::Properties => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let mut entries = Vec::new();
+                java_properties::PropertiesIter::new(reader)
+                    .read_into(|key, value| entries.push((key, value)))
+                    .map_err(serde::de::Error::custom)?;
+                let key_style = flat::KeyStyle {
+                    separator: '.',
+                    ignore_case: false,
+                    split_map_keys: true,
+                };
+                consume(&mut <dyn erased_serde::Deserializer>::erase(
+                    flat::FlatDeserializer::new(&entries, key_style),
+                ))
+            }),
+
+            #[cfg(feature = "dotenv")]
+            Self::DotEnv => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let entries = dotenvy::from_read_iter(reader)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(serde::de::Error::custom)?;
+                let key_style = flat::KeyStyle {
+                    separator: '_',
+                    ignore_case: true,
+                    split_map_keys: false,
+                };
+                consume(&mut <dyn erased_serde::Deserializer>::erase(
+                    flat::FlatDeserializer::new(&entries, key_style),
+                ))
+            }),
+
+            #[cfg(feature = "front-matter")]
+            Self::FrontMatter => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                use crate::sede::front_matter::{self, Delimiter};
+                let document = crate::util::read_document(reader)?;
+                let split = front_matter::split(&document).ok_or_else(|| {
+                    serde::de::Error::custom(
+                        "the document doesn't open with front matter between `---` or `+++` lines",
+                    )
+                })?;
+                match split.delimiter {
+                    Delimiter::Yaml => consume(&mut <dyn erased_serde::Deserializer>::erase(
+                        serde_yaml::Deserializer::from_str(split.front_matter),
+                    )),
+                    Delimiter::Toml => {
+                        let deserializer = toml::Deserializer::parse(split.front_matter)
+                            .map_err(serde::de::Error::custom)?;
+                        consume(&mut <dyn erased_serde::Deserializer>::erase(deserializer))
+                    }
+                }
+            }),
+
+            #[cfg(feature = "ion")]
+            Self::Ion => crate::MagicalDeserializer::from_fn(reader, |reader, consume| {
+                let bytes = crate::util::read_bytes(reader)?;
+                crate::util::consume_via_typed_api(consume, || ion_rs::serde::from_ion(bytes))
+            }),
+
+            #[cfg(feature = "avro")]
+            Self::Avro => avro::deserializer(reader, None),
         }
     }
 }
@@ -115,6 +536,32 @@ impl From<&SerializationFormat> for DeserializationFormat {
             SF::Rmp => Self::Rmp,
             SF::Bincode => Self::Bincode,
             SF::Pickle => Self::Pickle,
+            SF::PrettyToml => Self::Toml,
+            SF::CompactToml => Self::Toml,
+            SF::PrettyRon => Self::Ron,
+            SF::CompactRon => Self::Ron,
+            SF::Xml => Self::Xml,
+            SF::Csv => Self::Csv,
+            SF::Tsv => Self::Tsv,
+            SF::Bson => Self::Bson,
+            SF::Postcard => Self::Postcard,
+            SF::PostcardCobs => Self::PostcardCobs,
+            SF::Flexbuffers => Self::Flexbuffers,
+            SF::Sexpr => Self::Sexpr,
+            SF::ElispSexpr => Self::Sexpr,
+            SF::UrlEncoded => Self::UrlEncoded,
+            SF::JsonLines => Self::JsonLines,
+            SF::Bencode => Self::Bencode,
+            SF::PlistXml => Self::Plist,
+            SF::PlistBinary => Self::Plist,
+            SF::Ini => Self::Ini,
+            SF::Properties => Self::Properties,
+            SF::DotEnv => Self::DotEnv,
+            SF::YamlFrontMatter => Self::FrontMatter,
+            SF::TomlFrontMatter => Self::FrontMatter,
+            SF::IonText => Self::Ion,
+            SF::IonBinary => Self::Ion,
+            SF::Avro => Self::Avro,
         }
     }
 }
@@ -125,5 +572,22 @@ impl From<&Self> for DeserializationFormat {
     }
 }
 
+#[cfg(feature = "avro")]
+mod avro;
+#[cfg(any(
+    feature = "csv",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv"
+))]
+mod field;
+#[cfg(any(feature = "properties", feature = "dotenv"))]
+mod flat;
+#[cfg(feature = "ini")]
+mod ini;
+#[cfg(feature = "csv")]
+mod records;
 #[cfg(test)]
 mod test;
+#[cfg(feature = "xml")]
+mod xml;
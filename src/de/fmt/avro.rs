@@ -0,0 +1,42 @@
+//! Deserialization of Avro object container files.
+
+use std::{collections::VecDeque, io::Read};
+
+use apache_avro::{Reader, Schema, types::Value};
+use serde::de::Error as _;
+
+/// Reads the records of a container file, resolving them to the reader `schema`, if any,
+/// from the writer schema told by the file header.
+pub fn deserializer<'r, R: Read + 'r>(
+    reader: R,
+    schema: Option<Schema>,
+) -> crate::MagicalDeserializer<'r> {
+    // Records are read ahead, as the reader of the container file would borrow the schema.
+    let mut records: Option<VecDeque<Value>> = None;
+    crate::MagicalDeserializer::from_fn(reader, move |reader, consume| {
+        let records = match &mut records {
+            Some(records) => records,
+            None => records.insert(read_records(reader, schema.as_ref())?),
+        };
+        match records.pop_front() {
+            Some(record) => {
+                crate::util::consume_via_typed_api(consume, || apache_avro::from_value(&record))
+            }
+            // No more values.
+            None => Ok(()),
+        }
+    })
+}
+
+fn read_records(
+    reader: impl Read,
+    schema: Option<&Schema>,
+) -> Result<VecDeque<Value>, erased_serde::Error> {
+    match schema {
+        Some(schema) => Reader::with_schema(schema, reader),
+        None => Reader::new(reader),
+    }
+    .map_err(erased_serde::Error::custom)?
+    .collect::<Result<_, _>>()
+    .map_err(erased_serde::Error::custom)
+}
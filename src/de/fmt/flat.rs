@@ -0,0 +1,236 @@
+//! Deserialization of flat keys and values (e.g. Java properties, dotenv) as nested structs and
+//! maps, by splitting the keys at a separator.
+
+use serde::de::{
+    Error as _, IntoDeserializer, Visitor,
+    value::{Error, MapDeserializer},
+};
+
+use super::field::Field;
+
+/// How keys are split into the keys of nested structs and maps.
+#[derive(Clone, Copy)]
+pub struct KeyStyle {
+    pub separator: char,
+    /// Whether struct fields match keys regardless of their ASCII case.
+    pub ignore_case: bool,
+    /// Whether maps (as opposed to structs, whose fields are known) nest at separators.
+    pub split_map_keys: bool,
+}
+
+/// Presents the entries under the key `path`, either as a plain value or as a nested struct/map.
+#[derive(Clone)]
+pub struct FlatDeserializer<'a> {
+    entries: &'a [(String, String)],
+    style: KeyStyle,
+    /// Empty at the top level.
+    path: String,
+}
+
+impl<'a> FlatDeserializer<'a> {
+    pub fn new(entries: &'a [(String, String)], style: KeyStyle) -> Self {
+        Self {
+            entries,
+            style,
+            path: String::new(),
+        }
+    }
+
+    fn is_top_level(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    fn child(&self, key: &str) -> Self {
+        let path = if self.is_top_level() {
+            key.to_owned()
+        } else {
+            format!("{}{}{key}", self.path, self.style.separator)
+        };
+        Self { path, ..*self }
+    }
+
+    /// Strips `prefix` from `key`, regarding the key style.
+    fn strip_prefix<'k>(&self, key: &'k str, prefix: &str) -> Option<&'k str> {
+        let head = key.get(..prefix.len())?;
+        let matches = if self.style.ignore_case {
+            head.eq_ignore_ascii_case(prefix)
+        } else {
+            head == prefix
+        };
+        matches.then(|| &key[prefix.len()..])
+    }
+
+    /// The value of the key `path` itself.
+    fn value(&self) -> Option<Field<'a>> {
+        if self.is_top_level() {
+            return None;
+        }
+        self.entries
+            .iter()
+            .find(|(key, _)| self.strip_prefix(key, &self.path) == Some(""))
+            .map(|(_, value)| Field(value))
+    }
+
+    /// The keys nested under the key `path`, relative to it.
+    fn nested_keys(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let prefix = if self.is_top_level() {
+            String::new()
+        } else {
+            format!("{}{}", self.path, self.style.separator)
+        };
+        self.entries
+            .iter()
+            .filter_map(move |(key, _)| self.strip_prefix(key, &prefix))
+            .filter(|nested_key| !nested_key.is_empty())
+    }
+
+    fn has_nested_keys(&self) -> bool {
+        self.nested_keys().next().is_some()
+    }
+
+    fn leaf(&self) -> Result<Field<'a>, Error> {
+        self.value()
+            .ok_or_else(|| Error::custom(format!("expected a plain value at {:?}", self.path)))
+    }
+
+    fn visit_map<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut keys: Vec<&str> = Vec::new();
+        for nested_key in self.nested_keys() {
+            let key = match nested_key.split_once(self.style.separator) {
+                Some((key, _)) if self.style.split_map_keys => key,
+                _ => nested_key,
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        let entries = keys.into_iter().map(|key| (Field(key), self.child(key)));
+        visitor.visit_map(MapDeserializer::new(entries))
+    }
+
+    fn visit_struct<'de, V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let entries = fields
+            .iter()
+            .map(|&field| (Field(field), self.child(field)))
+            .filter(|(_, child)| child.value().is_some() || child.has_nested_keys())
+            .collect::<Vec<_>>();
+        visitor.visit_map(MapDeserializer::new(entries.into_iter()))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for FlatDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_leaf {
+    ($($method:ident($($arg:ident: $arg_ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $arg_ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                serde::Deserializer::$method(self.leaf()?, $($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> serde::Deserializer<'de> for FlatDeserializer<'_> {
+    type Error = Error;
+
+    /// Prefers the plain value of the key over the keys nested under it.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value() {
+            Some(field) => serde::Deserializer::deserialize_any(field, visitor),
+            None => self.visit_map(visitor),
+        }
+    }
+
+    deserialize_leaf! {
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value() {
+            Some(field) => serde::Deserializer::deserialize_option(field, visitor),
+            None if self.has_nested_keys() => visitor.visit_some(self),
+            None => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::custom("flat keys and values hold no sequences"))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.visit_map(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.visit_struct(fields, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
@@ -0,0 +1,110 @@
+//! Deserialization of an INI file as a map, whose sections are nested maps.
+
+use serde::de::{
+    IntoDeserializer, Visitor,
+    value::{Error, MapDeserializer},
+};
+
+use super::field::Field;
+
+/// Presents the keys of the general section and the named sections as entries of a single map.
+pub fn deserializer(
+    ini: &ini::Ini,
+) -> MapDeserializer<'_, std::vec::IntoIter<(Field<'_>, Entry<'_>)>, Error> {
+    let mut entries = Vec::new();
+    for (section_name, properties) in ini {
+        match section_name {
+            None => entries.extend(
+                properties
+                    .iter()
+                    .map(|(key, value)| (Field(key), Entry::Value(Field(value)))),
+            ),
+            Some(name) => entries.push((Field(name), Entry::Section(properties))),
+        }
+    }
+    MapDeserializer::new(entries.into_iter())
+}
+
+/// Either a value of a key or a whole section.
+#[derive(Clone, Copy)]
+pub enum Entry<'a> {
+    Value(Field<'a>),
+    Section(&'a ini::Properties),
+}
+
+impl<'a> Entry<'a> {
+    fn section_deserializer<'de>(
+        properties: &'a ini::Properties,
+    ) -> MapDeserializer<'de, impl Iterator<Item = (Field<'a>, Field<'a>)>, Error> {
+        MapDeserializer::new(
+            properties
+                .iter()
+                .map(|(key, value)| (Field(key), Field(value))),
+        )
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Entry<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! dispatch {
+    ($($method:ident($($arg:ident: $arg_ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $arg_ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                match self {
+                    Entry::Value(field) => field.$method($($arg,)* visitor),
+                    Entry::Section(properties) => {
+                        Self::section_deserializer(properties).$method($($arg,)* visitor)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> serde::Deserializer<'de> for Entry<'_> {
+    type Error = Error;
+
+    dispatch! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+}
@@ -0,0 +1,89 @@
+//! Deserialization of rows of a delimited text (CSV, TSV) as a sequence of records.
+
+use std::io::Read;
+
+use serde::de::{
+    DeserializeSeed, Error as _, SeqAccess, Visitor,
+    value::{Error, MapDeserializer},
+};
+
+use super::field::Field;
+
+const NOT_A_SEQUENCE: &str =
+    "delimited text holds a sequence of records, so it deserializes only into e.g. `Vec<Struct>`";
+
+/// Presents every row as a map from the header's column names to the row's fields.
+pub struct RecordsDeserializer<R> {
+    reader: csv::Reader<R>,
+}
+
+impl<R: Read> RecordsDeserializer<R> {
+    pub fn new(reader: R, delimiter: u8) -> Self {
+        Self {
+            reader: csv::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .from_reader(reader),
+        }
+    }
+}
+
+impl<'de, R: Read> serde::Deserializer<'de> for &mut RecordsDeserializer<R> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let headers = self.reader.headers().map_err(Error::custom)?.clone();
+        visitor.visit_seq(Records {
+            reader: &mut self.reader,
+            headers,
+            record: csv::StringRecord::new(),
+        })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::custom(NOT_A_SEQUENCE))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(Error::custom(NOT_A_SEQUENCE))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+struct Records<'a, R> {
+    reader: &'a mut csv::Reader<R>,
+    headers: csv::StringRecord,
+    record: csv::StringRecord,
+}
+
+impl<'de, R: Read> SeqAccess<'de> for Records<'_, R> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if !self
+            .reader
+            .read_record(&mut self.record)
+            .map_err(Error::custom)?
+        {
+            return Ok(None);
+        }
+        let fields = self
+            .headers
+            .iter()
+            .map(Field)
+            .zip(self.record.iter().map(Field));
+        seed.deserialize(MapDeserializer::new(fields)).map(Some)
+    }
+}
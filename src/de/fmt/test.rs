@@ -24,3 +24,413 @@ mod pickle {
         Ok(())
     }
 }
+
+#[cfg(feature = "jsonc")]
+mod jsonc {
+    #[test]
+    fn accepts_comments_and_trailing_commas() -> color_eyre::Result<()> {
+        let jsonc = b"// editor settings\n{\n  \"tabSize\": 4, /* spaces */\n}";
+        let deserialized: std::collections::HashMap<String, u32> =
+            crate::deserialize_magically(&jsonc[..], "JSONC")?;
+        assert_eq!(deserialized, [("tabSize".to_owned(), 4)].into());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_json5_only_syntax() {
+        let json5 = b"{tabSize: 0x4}";
+        let deserialized =
+            crate::deserialize_magically::<_, _, serde_json::Value>(&json5[..], "JSONC");
+        assert!(deserialized.is_err());
+    }
+
+    #[test]
+    fn rejects_empty_document_like_json() {
+        let empty = b"// nothing but a comment\n";
+        assert!(crate::deserialize_magically::<_, _, Option<u8>>(&empty[..], "JSONC").is_err());
+        assert!(crate::deserialize_magically::<_, _, Option<u8>>(&b""[..], "JSON").is_err());
+    }
+}
+
+#[cfg(feature = "json5")]
+mod json5 {
+    #[test]
+    fn accepts_unquoted_keys_and_hex_numbers() -> color_eyre::Result<()> {
+        let json5 = b"{tabSize: 0x4, 'indent': 'spaces',}";
+        let deserialized: serde_json::Value = crate::deserialize_magically(&json5[..], "JSON5")?;
+        assert_eq!(
+            deserialized,
+            serde_json::json!({"tabSize": 4, "indent": "spaces"})
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_large_integers_exact() -> color_eyre::Result<()> {
+        let deserialized: i64 = crate::deserialize_magically(&b"9153647491109197557"[..], "JSON5")?;
+        assert_eq!(deserialized, 9153647491109197557);
+        let nested: (i64, bool) =
+            crate::deserialize_magically(&b"[-8991815923204619264, true]"[..], "JSON5")?;
+        assert_eq!(nested, (-8991815923204619264, true));
+        assert!(crate::deserialize_magically::<_, _, i64>(&b"1.5"[..], "JSON5").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn accepts_non_finite_numbers() -> color_eyre::Result<()> {
+        let nan: f64 = crate::deserialize_magically(&b"NaN"[..], "JSON5")?;
+        assert!(nan.is_nan());
+        let infinities: Vec<f64> =
+            crate::deserialize_magically(&b"[Infinity, -Infinity]"[..], "JSON5")?;
+        assert_eq!(infinities, [f64::INFINITY, f64::NEG_INFINITY]);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "csv")]
+mod tsv {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Record {
+        name: String,
+        score: Option<u32>,
+    }
+
+    #[test]
+    fn reads_rows_by_header() -> color_eyre::Result<()> {
+        let tsv = b"score\tname\n1\ta\n\tb\n";
+        let records: Vec<Record> = crate::deserialize_magically(&tsv[..], "TSV")?;
+        assert_eq!(
+            records,
+            [
+                Record {
+                    name: "a".to_owned(),
+                    score: Some(1)
+                },
+                Record {
+                    name: "b".to_owned(),
+                    score: None
+                },
+            ]
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "urlencoded")]
+mod urlencoded {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Login {
+        user: String,
+        remember: bool,
+    }
+
+    #[test]
+    fn reads_form_body() -> color_eyre::Result<()> {
+        let body = b"user=J%C3%B3zef+K&remember=true";
+        let login: Login = crate::deserialize_magically(&body[..], "urlencoded")?;
+        assert_eq!(
+            login,
+            Login {
+                user: "Józef K".to_owned(),
+                remember: true
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn explains_nesting_limits() {
+        let body = b"login=user";
+        let result = crate::deserialize_magically::<_, _, std::collections::HashMap<String, Login>>(
+            &body[..],
+            "urlencoded",
+        );
+        let error = format!("{:#}", result.unwrap_err());
+        assert!(error.contains("nested structs, maps and sequences cannot be represented"));
+    }
+}
+
+#[cfg(feature = "jsonl")]
+mod jsonl {
+    #[test]
+    fn iterates_over_lines() -> color_eyre::Result<()> {
+        let json_lines = b"{\"id\":1}\n\n[2, 3]\r\n\"four\"";
+        let deserializer = crate::make_deserializer(&json_lines[..], "NDJSON")?;
+        let values = deserializer
+            .into_values::<serde_json::Value>()
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            values,
+            [
+                serde_json::json!({"id": 1}),
+                serde_json::json!([2, 3]),
+                serde_json::json!("four")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reports_exhausted_input() -> color_eyre::Result<()> {
+        let mut deserializer = crate::make_deserializer(&b"1\n"[..], "JSON-Lines")?;
+        assert_eq!(deserializer.deserialize::<u8>()?, 1);
+        assert!(deserializer.deserialize::<u8>().is_err());
+        assert_eq!(deserializer.deserialize_next::<u8>()?, None);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Manifest {
+        kind: String,
+    }
+
+    #[test]
+    fn iterates_over_documents() -> color_eyre::Result<()> {
+        let manifests = b"kind: Service\n---\nkind: Deployment\n";
+        let deserializer = crate::make_deserializer(&manifests[..], "YAML")?;
+        let kinds: Vec<_> = deserializer
+            .into_values::<Manifest>()
+            .map(|manifest| manifest.map(|m| m.kind))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(kinds, ["Service", "Deployment"]);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bencode")]
+mod bencode {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Torrent {
+        announce: String,
+        info: Info,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Info {
+        name: String,
+        #[serde(rename = "piece length")]
+        piece_length: u64,
+        length: u64,
+    }
+
+    #[test]
+    fn reads_torrent_file() -> color_eyre::Result<()> {
+        let path = std::env::temp_dir().join("sedes-reads-torrent-file.torrent");
+        std::fs::write(
+            &path,
+            "d8:announce18:http://tracker/ann4:infod6:lengthi42e4:name5:a.txt12:piece lengthi16384eee",
+        )?;
+        let torrent: Torrent = crate::deserialize_from_file(&path)?;
+        assert_eq!(
+            torrent,
+            Torrent {
+                announce: "http://tracker/ann".to_owned(),
+                info: Info {
+                    name: "a.txt".to_owned(),
+                    piece_length: 16384,
+                    length: 42
+                }
+            }
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "hocon")]
+mod hocon {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Service {
+        host: String,
+        port: u16,
+        url: String,
+    }
+
+    #[test]
+    fn resolves_substitutions() -> color_eyre::Result<()> {
+        let config = br#"
+            # Unquoted strings, comments and substitutions are HOCON-only.
+            host = localhost
+            port: 8080
+            url = "http://"${host}":"${port}
+        "#;
+        let service: Service = crate::deserialize_magically(&config[..], "HOCON")?;
+        assert_eq!(
+            service,
+            Service {
+                host: "localhost".to_owned(),
+                port: 8080,
+                url: "http://localhost:8080".to_owned()
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn has_no_serializer() {
+        let error = crate::DeserializationFormat::Hocon
+            .serializer(Vec::<u8>::new())
+            .err()
+            .expect("HOCON should be read-only");
+        assert_eq!(
+            error.to_string(),
+            "HOCON is a read-only format, so it has no serializer"
+        );
+
+        let path = std::env::temp_dir().join("sedes-has-no-serializer.conf");
+        assert!(crate::serialize_to_file(&path, "W", &1).is_err());
+        assert!(!path.exists());
+    }
+}
+
+#[cfg(feature = "ini")]
+mod ini {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        server: Server,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+        debug: bool,
+        proxy: Option<String>,
+    }
+
+    #[test]
+    fn reads_sections_as_nested_structs() -> color_eyre::Result<()> {
+        let config = b"; Comments are ignored.\nname = legacy\n\n[server]\nhost = C:\\srv\nport = 8080\ndebug = false\nproxy =\n";
+        let config: Config = crate::deserialize_magically(&config[..], "INI")?;
+        assert_eq!(
+            config,
+            Config {
+                name: "legacy".to_owned(),
+                server: Server {
+                    host: "C:\\srv".to_owned(),
+                    port: 8080,
+                    debug: false,
+                    proxy: None
+                }
+            }
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "front-matter")]
+mod front_matter {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Post {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    fn post() -> Post {
+        Post {
+            title: "Hello".to_owned(),
+            tags: vec!["rust".to_owned(), "serde".to_owned()],
+        }
+    }
+
+    #[test]
+    fn reads_yaml_front_matter() -> color_eyre::Result<()> {
+        let markdown = "---\ntitle: Hello\ntags: [rust, serde]\n---\n# Hello\n\n---\n";
+        let deserialized: Post = crate::deserialize_magically(markdown.as_bytes(), "Markdown")?;
+        assert_eq!(deserialized, post());
+        Ok(())
+    }
+
+    #[test]
+    fn reads_toml_front_matter() -> color_eyre::Result<()> {
+        let markdown = "+++\ntitle = \"Hello\"\ntags = [\"rust\", \"serde\"]\n+++\n# Hello\n";
+        let deserialized: Post = crate::deserialize_magically(markdown.as_bytes(), "Markdown")?;
+        assert_eq!(deserialized, post());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_markdown_without_front_matter() {
+        let deserialized =
+            crate::deserialize_magically::<_, _, Post>(&b"# Hello\n"[..], "Markdown");
+        assert!(deserialized.is_err());
+    }
+}
+
+#[cfg(feature = "ion")]
+mod ion {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Event {
+        id: u32,
+        source: String,
+    }
+
+    fn event() -> Event {
+        Event {
+            id: 7,
+            source: "lake".to_owned(),
+        }
+    }
+
+    #[test]
+    fn reads_text_encoding() -> color_eyre::Result<()> {
+        let deserialized: Event =
+            crate::deserialize_magically(&b"{id: 7, source: \"lake\"}"[..], "Ion")?;
+        assert_eq!(deserialized, event());
+        Ok(())
+    }
+
+    #[test]
+    fn reads_binary_encoding() -> color_eyre::Result<()> {
+        let path = std::env::temp_dir().join("sedes-reads-binary-encoding.10n");
+        std::fs::write(&path, ion_rs::serde::to_binary(&event())?)?;
+        let deserialized: Event = crate::deserialize_from_file(&path)?;
+        assert_eq!(deserialized, event());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "avro")]
+mod avro {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Order {
+        id: i64,
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct OrderV2 {
+        id: i64,
+        currency: String,
+    }
+
+    #[test]
+    fn resolves_records_to_reader_schema() -> color_eyre::Result<()> {
+        let writer_schema =
+            r#"{"type": "record", "name": "Order", "fields": [{"name": "id", "type": "long"}]}"#;
+        let reader_schema = r#"{"type": "record", "name": "Order", "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "currency", "type": "string", "default": "EUR"}
+        ]}"#;
+        let mut container = Vec::<u8>::new();
+        crate::SerializationFormat::Avro
+            .serializer_with_schema(&mut container, writer_schema)?
+            .serialize(&Order { id: 42 })?;
+
+        let order: Order = crate::deserialize_magically(container.as_slice(), "Avro")?;
+        assert_eq!(order, Order { id: 42 });
+        let order: OrderV2 = crate::DeserializationFormat::Avro
+            .deserializer_with_schema(container.as_slice(), reader_schema)?
+            .deserialize()?;
+        assert_eq!(
+            order,
+            OrderV2 {
+                id: 42,
+                currency: "EUR".to_owned()
+            }
+        );
+        Ok(())
+    }
+}
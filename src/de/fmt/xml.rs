@@ -0,0 +1,210 @@
+//! Deserialization of XML documents, reading sequences from the `<item>` elements of the root,
+//! as written by [`crate::SerializationFormat::Xml`].
+
+use serde::de::{DeserializeSeed, MapAccess, Visitor, value::SeqDeserializer};
+
+/// The element holding every item of a sequence inside the root element.
+const ITEM_TAG: &str = "item";
+
+/// Deserializes like the wrapped deserializer, but for sequences, whose items it unwraps.
+pub struct Items<D>(pub D);
+
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $arg_ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $arg_ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.0.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+/// Forwards to the deserializer of the borrowed value, for [`crate::MagicalDeserializer::new`].
+macro_rules! forward_by_ref {
+    ($($method:ident($($arg:ident: $arg_ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $arg_ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                Items(&mut self.0).$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_deserializer {
+    ($forward:ident! { $($custom:ident($($custom_arg:ident: $custom_ty:ty),*);)* }) => {
+        $forward! {
+            deserialize_any();
+            deserialize_bool();
+            deserialize_i8();
+            deserialize_i16();
+            deserialize_i32();
+            deserialize_i64();
+            deserialize_i128();
+            deserialize_u8();
+            deserialize_u16();
+            deserialize_u32();
+            deserialize_u64();
+            deserialize_u128();
+            deserialize_f32();
+            deserialize_f64();
+            deserialize_char();
+            deserialize_str();
+            deserialize_string();
+            deserialize_bytes();
+            deserialize_byte_buf();
+            deserialize_unit();
+            deserialize_unit_struct(name: &'static str);
+            deserialize_map();
+            deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+            deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+            deserialize_identifier();
+            deserialize_ignored_any();
+            $($custom($($custom_arg: $custom_ty),*);)*
+        }
+    };
+}
+
+impl<'de, D: serde::Deserializer<'de>> serde::Deserializer<'de> for Items<D> {
+    type Error = D::Error;
+
+    impl_deserializer!(forward! {});
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_option(UnwrapOption(visitor))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0
+            .deserialize_struct("", &[ITEM_TAG], UnwrapItems(ItemsSeed::Seq, visitor))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0
+            .deserialize_struct("", &[ITEM_TAG], UnwrapItems(ItemsSeed::Tuple(len), visitor))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+}
+
+impl<'de, R> serde::Deserializer<'de> for &mut Items<quick_xml::de::Deserializer<'de, R>>
+where
+    R: quick_xml::de::XmlRead<'de>,
+{
+    type Error = quick_xml::DeError;
+
+    impl_deserializer!(forward_by_ref! {
+        deserialize_option();
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+    });
+}
+
+/// Keeps unwrapping the items of a sequence inside an `Option`.
+struct UnwrapOption<V>(V);
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for UnwrapOption<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<V::Value, E> {
+        self.0.visit_none()
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<V::Value, E> {
+        self.0.visit_none()
+    }
+
+    fn visit_some<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.0.visit_some(Items(deserializer))
+    }
+}
+
+/// How the `<item>` elements are deserialized.
+#[derive(Clone, Copy)]
+enum ItemsSeed {
+    Seq,
+    Tuple(usize),
+}
+
+/// Hands the `<item>` elements of the root to the visitor of a sequence.
+struct UnwrapItems<V>(ItemsSeed, V);
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for UnwrapItems<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.1.expecting(formatter)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<V::Value, A::Error> {
+        let Self(seed, visitor) = self;
+        match map.next_key::<String>()? {
+            Some(key) if key == ITEM_TAG => {
+                let value = map.next_value_seed(UnwrapItems(seed, visitor))?;
+                match map.next_key::<String>()? {
+                    Some(key) => Err(not_an_item(&key)),
+                    None => Ok(value),
+                }
+            }
+            Some(key) => Err(not_an_item(&key)),
+            // An empty sequence.
+            None => visitor.visit_seq(SeqDeserializer::new(std::iter::empty::<()>())),
+        }
+    }
+}
+
+fn not_an_item<E: serde::de::Error>(tag: &str) -> E {
+    E::custom(format!(
+        "expected only <{ITEM_TAG}> elements in a sequence, found <{tag}>"
+    ))
+}
+
+impl<'de, V: Visitor<'de>> DeserializeSeed<'de> for UnwrapItems<V> {
+    type Value = V::Value;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        let Self(seed, visitor) = self;
+        match seed {
+            ItemsSeed::Seq => deserializer.deserialize_seq(visitor),
+            ItemsSeed::Tuple(len) => deserializer.deserialize_tuple(len, visitor),
+        }
+    }
+}
@@ -1,16 +1,45 @@
-use crate::{DeserializationFormat, SedeFormat};
+use crate::{Compression, DeserializationFormat, SedeFormat};
 use color_eyre::eyre::OptionExt as _;
 use color_eyre::{
     Result,
-    eyre::{Context, eyre},
+    eyre::{Context, bail, eyre},
 };
 use serde::de::DeserializeOwned;
 use std::ffi::OsStr;
+use std::io::Read;
 use std::path::Path;
 
+/// How the (de)serialization format of a file is detected.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    strum::EnumString,
+    strum::Display,
+    strum::VariantArray,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum Detection {
+    /// From the file extension, failing if it's missing or unknown.
+    #[default]
+    ExtensionOnly,
+    /// From the first bytes of the file, see [`DeserializationFormat::sniff`].
+    ContentOnly,
+    /// From the file extension, or from the first bytes of the file if it's missing or unknown.
+    ExtensionThenContent,
+    /// From the file extension, failing if the first bytes of the file contradict it,
+    /// see [`crate::de::sniff::contradiction`].
+    ContentVerifyingExtension,
+}
+
 /// Deserializes an object from a file, deducing the (de)serialization format from the file extension.
 ///
 /// E.g. for some_file.json, the format is deducted to be "JSON", for a file matching pattern /*.\.ya?ml/ it will be deducted as "YAML", etc.
+/// A compression extension (e.g. some_file.json.gz) is peeled off, and the file is decompressed,
+/// see [`Compression`].
 ///
 /// # Examples
 ///
@@ -29,22 +58,81 @@ use std::path::Path;
 /// let deserialized: Vec<i32> = sedes::deserialize_from_file(&path).unwrap();
 /// assert_eq!(deserialized, vec![1, 2, 42]);
 /// ```
-pub fn deserialize_from_file<'de, O>(path: impl AsRef<Path>) -> Result<O>
+pub fn deserialize_from_file<O>(path: impl AsRef<Path>) -> Result<O>
 where
     O: DeserializeOwned,
+{
+    deserialize_from_file_with_detection(path, Detection::ExtensionOnly)
+}
+
+/// Deserializes an object from a file, detecting the (de)serialization format as told.
+///
+/// # Examples
+///
+/// Deserialize JSON from a file without an extension.
+/// ```rust
+/// let path = std::env::temp_dir().join("example-upload");
+/// std::fs::write(&path, "[1, 2, 42]").unwrap();
+/// let deserialized: Vec<i32> =
+///     sedes::deserialize_from_file_with_detection(&path, "extension-then-content").unwrap();
+/// assert_eq!(deserialized, vec![1, 2, 42]);
+/// ```
+pub fn deserialize_from_file_with_detection<O, D>(path: impl AsRef<Path>, detection: D) -> Result<O>
+where
+    O: DeserializeOwned,
+    D: TryInto<Detection>,
+    color_eyre::Report: From<D::Error>,
 {
     let path: &Path = path.as_ref();
     (|| {
-        let deser_fmt: DeserializationFormat = (|| -> Result<DeserializationFormat> {
-            let file_ext: &OsStr = path.extension().ok_or_eyre("File extension not found.")?;
+        let detection: Detection = detection.try_into()?;
+        let (compression, decompressed_path) = Compression::peel(path);
+        // The extension is resolved first, so that the file is only peeked at if need be.
+        let ext_fmt = || -> Result<DeserializationFormat> {
+            let file_ext: &OsStr =
+                crate::util::file_ext(decompressed_path).ok_or_eyre("File extension not found.")?;
             SedeFormat::from_file_ext_os(file_ext)
                 .ok_or_else(|| eyre!("File extension not recognized: {file_ext:?}"))
-        })()
-        .context("Failed to deduce the deserialization format from the file extension.")?;
-
+                .context("Failed to deduce the deserialization format from the file extension.")
+        };
+        let ext_fmt = match detection {
+            Detection::ExtensionOnly | Detection::ContentVerifyingExtension => Some(ext_fmt()?),
+            Detection::ExtensionThenContent => ext_fmt().ok(),
+            Detection::ContentOnly => None,
+        };
         let file = std::fs::File::open(path).context("Failed to open the file for reading.")?;
-
+        let file: Box<dyn Read> = match compression {
+            Some(compression) => compression
+                .decoder(file)
+                .context("Failed to decompress the file.")?,
+            None => Box::new(file),
+        };
+        let (deser_fmt, file): (_, Box<dyn Read>) = match ext_fmt {
+            Some(ext_fmt) if detection != Detection::ContentVerifyingExtension => (ext_fmt, file),
+            ext_fmt => {
+                let file = crate::de::sniff::peek(file).context("Failed to read the file.")?;
+                let bytes = file.peeked();
+                let deser_fmt = match ext_fmt {
+                    Some(ext_fmt) => match crate::de::sniff::contradiction(ext_fmt, bytes) {
+                        Some(content_fmt) => bail!(
+                            "The extension says {ext_fmt} but the content looks like {content_fmt}."
+                        ),
+                        None => ext_fmt,
+                    },
+                    None => DeserializationFormat::sniff(bytes)
+                        .first()
+                        .copied()
+                        .ok_or_eyre(
+                            "Failed to deduce the deserialization format from the file content.",
+                        )?,
+                };
+                (deser_fmt, Box::new(file))
+            }
+        };
         crate::deserialize_magically::<_, DeserializationFormat, O>(file, deser_fmt)
     })()
     .wrap_err_with(|| format!("failed to deserialize an object from a file {path:?}"))
 }
+
+#[cfg(all(test, feature = "json", feature = "toml"))]
+mod test;
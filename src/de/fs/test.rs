@@ -0,0 +1,62 @@
+use crate::{Detection, deserialize_from_file_with_detection};
+
+fn write_temp(file_name: &str, content: impl AsRef<[u8]>) -> std::io::Result<std::path::PathBuf> {
+    let path = std::env::temp_dir().join(file_name);
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+#[test]
+fn falls_back_to_content() -> color_eyre::Result<()> {
+    let path = write_temp("sedes-falls-back-to-content.bin", "[1, 2, 42]")?;
+    assert!(deserialize_from_file_with_detection::<Vec<i32>, _>(&path, "extension-only").is_err());
+    let deserialized: Vec<i32> =
+        deserialize_from_file_with_detection(&path, "extension-then-content")?;
+    assert_eq!(deserialized, [1, 2, 42]);
+    let deserialized: Vec<i32> = deserialize_from_file_with_detection(&path, "content-only")?;
+    assert_eq!(deserialized, [1, 2, 42]);
+    Ok(())
+}
+
+#[test]
+fn verifies_extension_against_content() -> color_eyre::Result<()> {
+    let path = write_temp(
+        "sedes-verifies-extension-against-content.toml",
+        "{\"a\": 1}",
+    )?;
+    let error = deserialize_from_file_with_detection::<serde_json::Value, _>(
+        &path,
+        Detection::ContentVerifyingExtension,
+    )
+    .unwrap_err();
+    assert!(
+        format!("{error:?}").contains("The extension says TOML but the content looks like JSON.")
+    );
+    Ok(())
+}
+
+#[cfg(all(feature = "bincode", feature = "postcard"))]
+#[test]
+fn trusts_extension_of_formats_without_signature() -> color_eyre::Result<()> {
+    // The length prefix looks like a MessagePack array.
+    let value = vec![7u8; 150];
+    for (fmt, file_ext) in [("Bincode", "bincode"), ("Postcard", "postcard")] {
+        let mut content = Vec::new();
+        crate::serialize_magically(&mut content, fmt, &value)?;
+        let path = write_temp(&format!("sedes-trusts-extension.{file_ext}"), content)?;
+        let deserialized: Vec<u8> =
+            deserialize_from_file_with_detection(&path, Detection::ContentVerifyingExtension)?;
+        assert_eq!(deserialized, value);
+    }
+    Ok(())
+}
+
+#[test]
+fn resolves_extension_before_opening_file() {
+    let error = deserialize_from_file_with_detection::<serde_json::Value, _>(
+        "sedes-nonexistent-file.unknown-ext",
+        "extension-only",
+    )
+    .unwrap_err();
+    assert!(format!("{error:?}").contains("File extension not recognized"));
+}
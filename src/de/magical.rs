@@ -1,11 +1,37 @@
-use std::{error::Error, mem::ManuallyDrop, pin::Pin};
+use std::{error::Error, io::Read, mem::ManuallyDrop, pin::Pin};
+
+/// Receives a deserializer built by a deferred deserialization function.
+pub type ErasedConsumer<'c> = dyn for<'de> FnMut(&mut dyn erased_serde::Deserializer<'de>) -> Result<(), erased_serde::Error>
+    + 'c;
+
+type DeserializeFn<'r> =
+    Box<dyn FnMut(&mut ErasedConsumer<'_>) -> Result<(), erased_serde::Error> + 'r>;
 
 pub struct MagicalDeserializer<'r> {
-    /// Don't R/W from this address until dropping the "dependant",
-    /// which uniquely borrows the addressed value.
-    boxed_dependency: *mut (dyn crate::util::Something + 'r),
-    /// Should be dropped first. Should not be taken out of the field.
-    erased_dependant: ManuallyDrop<Pin<Box<dyn erased_serde::Deserializer<'r> + 'r>>>,
+    backend: Backend<'r>,
+    /// Whether any value was deserialized yet.
+    started: bool,
+}
+
+enum Backend<'r> {
+    Erased {
+        /// Don't R/W from this address until dropping the "dependant",
+        /// which uniquely borrows the addressed value.
+        boxed_dependency: *mut (dyn crate::util::Something + 'r),
+        /// Should be dropped first. Should not be taken out of the field.
+        erased_dependant: ManuallyDrop<Pin<Box<dyn erased_serde::Deserializer<'r> + 'r>>>,
+    },
+    /// Builds a fresh deserializer for every [`MagicalDeserializer::deserialize`] call.
+    Deferred(DeserializeFn<'r>),
+}
+
+impl<'r> From<Backend<'r>> for MagicalDeserializer<'r> {
+    fn from(backend: Backend<'r>) -> Self {
+        Self {
+            backend,
+            started: false,
+        }
+    }
 }
 
 impl<'r> MagicalDeserializer<'r> {
@@ -13,12 +39,13 @@ impl<'r> MagicalDeserializer<'r> {
     where
         T: serde::Deserializer<'r> + 'r,
     {
-        Self {
+        Backend::Erased {
             boxed_dependency: Box::leak(Box::new(())),
             erased_dependant: ManuallyDrop::new(Box::pin(<dyn erased_serde::Deserializer>::erase(
                 typed_deserializer,
             ))),
         }
+        .into()
     }
 
     pub fn new<T>(typed_deserializer: T) -> Self
@@ -27,28 +54,130 @@ impl<'r> MagicalDeserializer<'r> {
         &'r mut T: serde::Deserializer<'r>,
     {
         let boxed_dependency: *mut T = Box::leak(Box::new(typed_deserializer));
-        Self {
+        Backend::Erased {
             boxed_dependency,
             erased_dependant: ManuallyDrop::new(Box::pin(<dyn erased_serde::Deserializer>::erase(
                 unsafe { &mut *boxed_dependency },
             ))),
         }
+        .into()
+    }
+
+    /// Wraps a function that deserializes from the reader by itself.
+    ///
+    /// Meant for formats that can only deserialize from a buffered document (e.g. `&str`)
+    /// and for streams of values (e.g. JSON Lines).
+    /// The function should hand exactly one deserializer to the given consumer,
+    /// or none if the input holds no more values.
+    pub fn from_fn<R, F>(mut reader: R, mut deserialize_fn: F) -> Self
+    where
+        R: Read + 'r,
+        F: FnMut(&mut R, &mut ErasedConsumer<'_>) -> Result<(), erased_serde::Error> + 'r,
+    {
+        Backend::Deferred(Box::new(move |consumer| {
+            deserialize_fn(&mut reader, consumer)
+        }))
+        .into()
+    }
+
+    /// Wraps deserializers of consecutive values, e.g. of the documents in a YAML stream.
+    pub fn from_deserializers<I>(deserializers: I) -> Self
+    where
+        I: IntoIterator<IntoIter: 'r>,
+        I::Item: serde::Deserializer<'r>,
+    {
+        let mut deserializers = deserializers.into_iter();
+        Backend::Deferred(Box::new(move |consume| match deserializers.next() {
+            Some(deserializer) => {
+                consume(&mut <dyn erased_serde::Deserializer>::erase(deserializer))
+            }
+            // No more values.
+            None => Ok(()),
+        }))
+        .into()
+    }
+
+    pub fn deserialize<O: serde::de::DeserializeOwned>(
+        &mut self,
+    ) -> Result<O, impl Error + 'static + use<O>> {
+        self.deserialize_erased()
+    }
+
+    fn deserialize_erased<O: serde::de::DeserializeOwned>(
+        &mut self,
+    ) -> Result<O, erased_serde::Error> {
+        self.started = true;
+        match &mut self.backend {
+            Backend::Erased {
+                erased_dependant, ..
+            } => erased_serde::deserialize(unsafe {
+                // We trust the called function to not take the value out of the field.
+                erased_dependant.as_mut().get_unchecked_mut()
+            }),
+            Backend::Deferred(deserialize_fn) => Self::deserialize_deferred(deserialize_fn)?
+                .ok_or_else(|| serde::de::Error::custom("the input holds no more values")),
+        }
     }
 
-    pub fn deserialize<'o, O: serde::de::DeserializeOwned>(
+    /// Deserializes the next value, or returns `None` if the input holds no more values.
+    ///
+    /// Streaming formats (e.g. JSON Lines) yield all their values one by one,
+    /// while the others yield the single value they hold.
+    pub fn deserialize_next<O: serde::de::DeserializeOwned>(
         &mut self,
-    ) -> Result<O, impl Error + 'static> {
-        erased_serde::deserialize(unsafe {
-            // We trust the called function to not take the value out of the field.
-            self.erased_dependant.as_mut().get_unchecked_mut()
-        })
+    ) -> Result<Option<O>, impl Error + 'static + use<O>> {
+        match &mut self.backend {
+            Backend::Erased { .. } if self.started => Ok(None),
+            Backend::Erased { .. } => self.deserialize_erased().map(Some),
+            Backend::Deferred(deserialize_fn) => {
+                self.started = true;
+                Self::deserialize_deferred(deserialize_fn)
+            }
+        }
+    }
+
+    /// Turns into an iterator of all values held by the input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[cfg(feature = "jsonl")]
+    /// {
+    ///     let json_lines = b"{\"id\":1}\n{\"id\":2}\n";
+    ///     let deserializer = sedes::make_deserializer(&json_lines[..], "JSON-Lines").unwrap();
+    ///     let ids: Vec<serde_json::Value> =
+    ///         deserializer.into_values().collect::<Result<_, _>>().unwrap();
+    ///     assert_eq!(ids, [serde_json::json!({"id": 1}), serde_json::json!({"id": 2})]);
+    /// }
+    /// ```
+    pub fn into_values<O: serde::de::DeserializeOwned>(
+        mut self,
+    ) -> impl Iterator<Item = Result<O, impl Error + 'static + use<'r, O>>> + 'r {
+        std::iter::from_fn(move || self.deserialize_next().transpose())
+    }
+
+    fn deserialize_deferred<O: serde::de::DeserializeOwned>(
+        deserialize_fn: &mut DeserializeFn<'r>,
+    ) -> Result<Option<O>, erased_serde::Error> {
+        let mut deserialized = None;
+        deserialize_fn(&mut |deserializer| {
+            deserialized = Some(erased_serde::deserialize(deserializer)?);
+            Ok(())
+        })?;
+        Ok(deserialized)
     }
 }
 impl<'r> Drop for MagicalDeserializer<'r> {
     fn drop(&mut self) {
-        unsafe {
-            drop(ManuallyDrop::take(&mut self.erased_dependant));
-            drop(Box::from_raw(self.boxed_dependency));
+        if let Backend::Erased {
+            boxed_dependency,
+            erased_dependant,
+        } = &mut self.backend
+        {
+            unsafe {
+                drop(ManuallyDrop::take(erased_dependant));
+                drop(Box::from_raw(*boxed_dependency));
+            }
         }
     }
 }
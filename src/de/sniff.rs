@@ -0,0 +1,287 @@
+//! Guessing the format of an input from its first bytes.
+
+use std::io::{Chain, Cursor, Read};
+
+use crate::DeserializationFormat as DF;
+
+/// How many bytes are peeked at to guess the format of an input.
+pub const PEEK_LEN: usize = 1024;
+
+/// Reads the first bytes of the input, which are then read again ahead of the rest of the input.
+pub fn peek<R: Read>(mut reader: R) -> std::io::Result<Peeked<R>> {
+    let mut peeked = Vec::with_capacity(PEEK_LEN);
+    reader
+        .by_ref()
+        .take(PEEK_LEN as u64)
+        .read_to_end(&mut peeked)?;
+    Ok(Peeked(Cursor::new(peeked).chain(reader)))
+}
+
+/// An input whose first bytes were peeked at, see [`peek`].
+pub struct Peeked<R>(Chain<Cursor<Vec<u8>>, R>);
+
+impl<R> Peeked<R> {
+    /// The first bytes of the input, up to [`PEEK_LEN`].
+    pub fn peeked(&self) -> &[u8] {
+        self.0.get_ref().0.get_ref()
+    }
+
+    /// Whether the peeked bytes are the whole input, i.e. fewer than [`PEEK_LEN`].
+    pub fn is_whole(&self) -> bool {
+        self.peeked().len() < PEEK_LEN
+    }
+}
+
+impl<R: Read> Read for Peeked<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// Ranks guesses, skipping duplicates and formats whose features are disabled.
+struct Guesses(Vec<DF>);
+
+macro_rules! guess {
+    ($guesses:expr, $($feature:tt => $variant:ident),+ $(,)?) => {{
+        $(
+            #[cfg(feature = $feature)]
+            $guesses.push(DF::$variant);
+        )+
+    }};
+}
+
+impl Guesses {
+    fn push(&mut self, fmt: DF) {
+        if !self.0.contains(&fmt) {
+            self.0.push(fmt);
+        }
+    }
+}
+
+/// Guesses the format from the first bytes of an input, from the most to the least likely.
+///
+/// Formats with neither a signature nor a textual look (e.g. Bincode, Postcard) are never guessed.
+pub fn sniff(bytes: &[u8]) -> Vec<DF> {
+    let mut guesses = Guesses(Vec::new());
+    sniff_signature(bytes, &mut guesses);
+    match text_prefix(bytes) {
+        Some(text) => sniff_text(text, &mut guesses),
+        None => sniff_binary(bytes, &mut guesses),
+    }
+    guesses.0
+}
+
+/// The format the first bytes of an input look like, if they contradict the `claimed` one.
+///
+/// This is more cautious than [`sniff`]: a magic number of another format contradicts
+/// any claim, but the look of text only contradicts a format that is recognized by its look.
+/// So formats with neither (e.g. Bincode, Postcard) are never contradicted without a magic number.
+pub fn contradiction(claimed: DF, bytes: &[u8]) -> Option<DF> {
+    let mut guesses = Guesses(Vec::new());
+    sniff_signature(bytes, &mut guesses);
+    if guesses.0.is_empty() {
+        match text_prefix(bytes) {
+            Some(text) if textual_formats().0.contains(&claimed) => sniff_text(text, &mut guesses),
+            _ => return None,
+        }
+    }
+    match guesses.0.first() {
+        Some(&guessed) if !guesses.0.contains(&claimed) => Some(guessed),
+        _ => None,
+    }
+}
+
+/// The formats [`sniff_text`] can tell by their look.
+#[allow(unused_mut)]
+fn textual_formats() -> Guesses {
+    let mut formats = Guesses(Vec::new());
+    guess!(
+        formats,
+        "json" => Json,
+        "json5" => Json5,
+        "jsonc" => Jsonc,
+        "jsonl" => JsonLines,
+        "yaml" => Yaml,
+        "toml" => Toml,
+        "xml" => Xml,
+        "csv" => Csv,
+        "csv" => Tsv,
+        "sexpr" => Sexpr,
+        "urlencoded" => UrlEncoded,
+        "bencode" => Bencode,
+        "plist" => Plist,
+        "hocon" => Hocon,
+        "ini" => Ini,
+        "properties" => Properties,
+        "dotenv" => DotEnv,
+        "front-matter" => FrontMatter,
+    );
+    formats
+}
+
+/// Recognizes magic numbers.
+#[allow(unused_variables)]
+fn sniff_signature(bytes: &[u8], guesses: &mut Guesses) {
+    match bytes {
+        // Protocol 2+ header.
+        [0x80, 2..=5, ..] => guess!(guesses, "pickle" => Pickle),
+        // Self-described CBOR tag.
+        [0xd9, 0xd9, 0xf7, ..] => guess!(guesses, "cbor" => Cbor),
+        [b'O', b'b', b'j', 1, ..] => guess!(guesses, "avro" => Avro),
+        // Binary version marker.
+        [0xe0, 1, 0, 0xea, ..] => guess!(guesses, "ion" => Ion),
+        [b'b', b'p', b'l', b'i', b's', b't', ..] => guess!(guesses, "plist" => Plist),
+        _ => {}
+    }
+}
+
+/// Recognizes the leading markers of binary formats.
+#[allow(unused_variables)]
+fn sniff_binary(bytes: &[u8], guesses: &mut Guesses) {
+    match bytes.first() {
+        // MessagePack fixmap, map 16/32, fixarray and array 16/32, which are CBOR arrays too.
+        Some(0x80..=0x9f | 0xdc..=0xdf) => guess!(guesses, "rmp" => Rmp, "cbor" => Cbor),
+        // CBOR maps, which are MessagePack fixstr too.
+        Some(0xa0..=0xbf) => guess!(guesses, "cbor" => Cbor, "rmp" => Rmp),
+        _ => {}
+    }
+    // A document told to span the whole input.
+    let told_len = match *bytes {
+        [a, b, c, d, .., 0] => Some(i32::from_le_bytes([a, b, c, d]) as usize),
+        _ => None,
+    };
+    if told_len == Some(bytes.len()) {
+        guess!(guesses, "bson" => Bson);
+    }
+}
+
+/// The input as text, even if the peeked bytes cut a character in two.
+fn text_prefix(bytes: &[u8]) -> Option<&str> {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(error) if error.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..error.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let is_binary = text
+        .chars()
+        .any(|c| c.is_control() && !c.is_ascii_whitespace());
+    (!text.trim().is_empty() && !is_binary).then_some(text)
+}
+
+/// Recognizes the look of the first meaningful lines of text formats.
+#[allow(unused_variables)]
+fn sniff_text(text: &str, guesses: &mut Guesses) {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(['#', ';']));
+    let Some(line) = lines.next() else {
+        // Only comments.
+        guess!(guesses, "yaml" => Yaml, "toml" => Toml, "ini" => Ini);
+        return;
+    };
+
+    if text.starts_with("+++") {
+        guess!(guesses, "front-matter" => FrontMatter);
+    } else if text.starts_with("---") {
+        guess!(guesses, "yaml" => Yaml, "front-matter" => FrontMatter);
+    } else if line.starts_with('{') {
+        if lines.next().is_some_and(|line| line.starts_with('{')) {
+            guess!(guesses, "jsonl" => JsonLines);
+        }
+        guess!(
+            guesses,
+            "json" => Json,
+            "json5" => Json5,
+            "jsonc" => Jsonc,
+            "jsonl" => JsonLines,
+            "yaml" => Yaml,
+            "hocon" => Hocon,
+        );
+    } else if is_section_header(line) {
+        guess!(guesses, "toml" => Toml, "ini" => Ini);
+    } else if line.starts_with('[') {
+        guess!(guesses, "json" => Json, "json5" => Json5, "jsonc" => Jsonc, "yaml" => Yaml);
+    } else if line.starts_with('<') {
+        if text.contains("<plist") || text.contains("<!DOCTYPE plist") {
+            guess!(guesses, "plist" => Plist);
+        }
+        guess!(guesses, "xml" => Xml);
+    } else if line.starts_with('(') {
+        guess!(guesses, "sexpr" => Sexpr);
+    } else if is_bencoded(line) {
+        guess!(guesses, "bencode" => Bencode);
+    } else if line.starts_with("- ") || line == "-" {
+        guess!(guesses, "yaml" => Yaml);
+    } else if line.starts_with(['"', '-']) || line.starts_with(|c: char| c.is_ascii_digit()) {
+        guess!(guesses, "json" => Json, "yaml" => Yaml);
+    } else if !line.contains(char::is_whitespace) && line.contains('=') && line.contains('&') {
+        guess!(guesses, "urlencoded" => UrlEncoded);
+    } else if let Some((key, value)) = line.split_once('=') {
+        let (key, value) = (key.trim(), value.trim());
+        if key.starts_with("export ") || is_env_name(key) {
+            guess!(guesses, "dotenv" => DotEnv);
+        } else if looks_like_toml_value(value) {
+            guess!(guesses, "toml" => Toml);
+        }
+        guess!(
+            guesses,
+            "properties" => Properties,
+            "ini" => Ini,
+            "toml" => Toml,
+            "dotenv" => DotEnv,
+            "hocon" => Hocon,
+        );
+    } else if line.ends_with(':') || line.contains(": ") {
+        guess!(guesses, "yaml" => Yaml, "properties" => Properties, "hocon" => Hocon);
+    } else if line.contains('\t') {
+        guess!(guesses, "csv" => Tsv);
+    } else if line.contains(',') {
+        guess!(guesses, "csv" => Csv);
+    } else if ["true", "false", "null"].contains(&line) {
+        guess!(guesses, "json" => Json, "yaml" => Yaml);
+    } else {
+        guess!(guesses, "yaml" => Yaml);
+    }
+}
+
+/// E.g. `[server]` or `[servers.alpha]`, but not `[1, 2]`.
+fn is_section_header(line: &str) -> bool {
+    line.strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .is_some_and(|name| {
+            name.starts_with(|c: char| c.is_alphabetic() || c == '[')
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "_-. []".contains(c))
+        })
+}
+
+/// E.g. `d8:announce...`, `li42e...` or `i42e`.
+fn is_bencoded(line: &str) -> bool {
+    let mut chars = line.chars();
+    match (chars.next(), chars.next()) {
+        (Some('d' | 'l'), Some(next)) => next.is_ascii_digit() || "ild".contains(next),
+        (Some('i'), Some(next)) => next.is_ascii_digit() || next == '-',
+        _ => false,
+    }
+}
+
+/// E.g. `DATABASE_URL`.
+fn is_env_name(key: &str) -> bool {
+    key.chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Quoted strings, numbers, booleans, arrays and inline tables.
+fn looks_like_toml_value(value: &str) -> bool {
+    value.starts_with(['"', '\'', '[', '{'])
+        || value.parse::<f64>().is_ok()
+        || ["true", "false"].contains(&value)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,87 @@
+use crate::DeserializationFormat as DF;
+
+fn top_guess(bytes: &[u8]) -> Option<DF> {
+    DF::sniff(bytes).first().copied()
+}
+
+#[cfg(feature = "pickle")]
+#[test]
+fn recognizes_pickle_protocol_header() -> color_eyre::Result<()> {
+    let bytes = serde_pickle::to_vec(&(1, true), serde_pickle::SerOptions::new())?;
+    assert_eq!(top_guess(&bytes), Some(DF::Pickle));
+    Ok(())
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn recognizes_cbor_self_describe_tag() -> color_eyre::Result<()> {
+    let mut bytes = Vec::new();
+    let mut serializer = serde_cbor::Serializer::new(&mut bytes);
+    serializer.self_describe()?;
+    serde::Serialize::serialize(&[1, 2], &mut serializer)?;
+    assert_eq!(top_guess(&bytes), Some(DF::Cbor));
+    Ok(())
+}
+
+#[cfg(feature = "rmp")]
+#[test]
+fn recognizes_messagepack_markers() -> color_eyre::Result<()> {
+    let bytes = rmp_serde::to_vec(&[1, 2, 3])?;
+    assert_eq!(top_guess(&bytes), Some(DF::Rmp));
+    Ok(())
+}
+
+#[cfg(all(
+    feature = "json",
+    feature = "yaml",
+    feature = "toml",
+    feature = "dotenv"
+))]
+#[test]
+fn recognizes_text_formats() {
+    assert_eq!(top_guess(b"  {\"a\": [1, 2]}"), Some(DF::Json));
+    assert_eq!(top_guess(b"[1, 2]"), Some(DF::Json));
+    assert_eq!(top_guess(b"# Service\nkind: Service\n"), Some(DF::Yaml));
+    assert_eq!(top_guess(b"- 1\n- 2\n"), Some(DF::Yaml));
+    assert_eq!(top_guess(b"[server]\nport = 8080\n"), Some(DF::Toml));
+    assert_eq!(top_guess(b"title = \"Hello\"\n"), Some(DF::Toml));
+    assert_eq!(top_guess(b"DATABASE_URL=postgres://db\n"), Some(DF::DotEnv));
+    assert_eq!(top_guess(b"\x00\x01"), None);
+}
+
+#[cfg(feature = "jsonl")]
+#[test]
+fn prefers_json_lines_for_objects_on_many_lines() {
+    assert_eq!(top_guess(b"{\"a\": 1}\n{\"a\": 2}\n"), Some(DF::JsonLines));
+    assert_eq!(top_guess(b"{\n  \"a\": 1\n}"), Some(DF::Json));
+}
+
+#[cfg(all(feature = "cbor", feature = "rmp"))]
+#[test]
+fn tries_less_likely_guesses_for_whole_input() -> color_eyre::Result<()> {
+    let bytes = serde_cbor::to_vec(&vec![1, 2, 3])?;
+    assert_eq!(DF::sniff(&bytes), [DF::Rmp, DF::Cbor]);
+    let deserialized: Vec<u8> = crate::deserialize_sniffed(bytes.as_slice())?;
+    assert_eq!(deserialized, [1, 2, 3]);
+    Ok(())
+}
+
+#[cfg(all(feature = "cbor", feature = "rmp"))]
+#[test]
+fn reports_ambiguity_of_longer_input() -> color_eyre::Result<()> {
+    // MessagePack would read it as a shorter array.
+    let bytes = serde_cbor::to_vec(&vec![1; super::PEEK_LEN])?;
+    let error = crate::deserialize_sniffed::<_, Vec<u8>>(bytes.as_slice()).unwrap_err();
+    assert!(error.to_string().contains("RMP, CBOR"), "{error}");
+    Ok(())
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn deserialized_input_keeps_peeked_bytes() -> color_eyre::Result<()> {
+    // Longer than the peeked prefix.
+    let yaml: String = (0..super::PEEK_LEN).map(|i| format!("- {i}\n")).collect();
+    let deserialized: Vec<usize> = crate::deserialize_sniffed(yaml.as_bytes())?;
+    assert_eq!(deserialized, (0..super::PEEK_LEN).collect::<Vec<_>>());
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use std::{io::Read, io::Write};
 pub use {
     de::{
-        deserialize_magically, fmt::DeserializationFormat, fs::deserialize_from_file,
-        magical::MagicalDeserializer, make_deserializer,
+        deserialize_magically, deserialize_sniffed,
+        fmt::DeserializationFormat,
+        fs::{Detection, deserialize_from_file, deserialize_from_file_with_detection},
+        magical::MagicalDeserializer,
+        make_deserializer,
     },
     se::{
         fmt::SerializationFormat, fs::serialize_to_file, magical::MagicalSerializer,
@@ -12,7 +15,7 @@ pub use {
 
 pub mod de;
 pub mod se;
-pub use sede::fmt::SedeFormat;
+pub use sede::{compound::Compound, compression::Compression, fmt::SedeFormat};
 pub mod sede;
 mod util;
 
@@ -23,12 +26,12 @@ pub fn translate_magically<'r, 'w, T, R, W, I, O>(
     output_fmt: O,
 ) -> color_eyre::Result<()>
 where
-    T: serde::de::DeserializeOwned + serde::Serialize + ?Sized,
+    T: serde::de::DeserializeOwned + serde::Serialize,
     R: Read + 'r,
     W: Write + 'w,
-    I: TryInto<crate::DeserializationFormat>,
+    I: TryInto<crate::Compound<crate::DeserializationFormat>>,
     color_eyre::Report: From<I::Error>,
-    O: TryInto<crate::SerializationFormat>,
+    O: TryInto<crate::Compound<crate::SerializationFormat>>,
     color_eyre::Report: From<O::Error>,
 {
     let value: T = deserialize_magically(reader, input_fmt)?;
@@ -44,9 +47,22 @@ pub mod test {
 
     type Serializable = (i64, f32, bool);
 
+    /// [`Serializable`] with named fields, for formats that need a struct or a map at the top
+    /// level (e.g. TOML) or hold only records (e.g. CSV).
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Record {
+        int: i64,
+        float: f32,
+        boolean: bool,
+    }
+
     #[test]
     fn sede_bijectivity() -> color_eyre::Result<()> {
         for fmt in crate::DeserializationFormat::VARIANTS {
+            if crate::SerializationFormat::try_from(fmt).is_err() {
+                // Read-only formats can't be round-tripped.
+                continue;
+            }
             for _ in 0..5 {
                 test_bijectivity_for(fmt).with_context(|| format!("failed for {fmt}"))?;
             }
@@ -56,14 +72,78 @@ pub mod test {
 
     fn test_bijectivity_for(fmt: &crate::DeserializationFormat) -> color_eyre::Result<()> {
         let mut rng = rand::rng();
-        let mut sink = Vec::<u8>::new();
 
         let serializable: Serializable = Rng::random(&mut rng);
+        let (int, float, boolean) = serializable;
+        let record = Record {
+            int,
+            float,
+            boolean,
+        };
+
+        #[cfg(feature = "bencode")]
+        if fmt.is_bencode() {
+            // Bencode has neither floats nor booleans, but has byte strings.
+            return assert_round_trip(fmt, &(int, boolean.to_string()));
+        }
+        #[cfg(feature = "avro")]
+        if fmt.is_avro() {
+            // Avro needs a schema to write.
+            let schema = r#"{"type": "record", "name": "Record", "fields": [
+                {"name": "int", "type": "long"},
+                {"name": "float", "type": "float"},
+                {"name": "boolean", "type": "boolean"}
+            ]}"#;
+            let mut sink = Vec::<u8>::new();
+            crate::SerializationFormat::Avro
+                .serializer_with_schema(&mut sink, schema)?
+                .serialize(&record)?;
+            let deserialized: Record = crate::deserialize_magically(sink.as_slice(), fmt)?;
+            assert_eq!(deserialized, record);
+            return Ok(());
+        }
+        #[cfg(feature = "csv")]
+        if fmt.is_csv() || fmt.is_tsv() {
+            // Delimited text holds only sequences of records.
+            return assert_round_trip(fmt, &vec![record]);
+        }
+        if needs_table(fmt) {
+            return assert_round_trip(fmt, &record);
+        }
+        assert_round_trip(fmt, &serializable)
+    }
 
-        sink.clear();
-        crate::serialize_magically(&mut sink, fmt, &serializable)?;
-        let deserialized: Serializable = crate::deserialize_magically(sink.as_slice(), fmt)?;
-        assert_eq!(deserialized, serializable);
+    /// Whether the format needs a struct or a map at the top level.
+    fn needs_table(fmt: &crate::DeserializationFormat) -> bool {
+        use crate::DeserializationFormat as DF;
+        match fmt {
+            #[cfg(feature = "toml")]
+            DF::Toml => true,
+            #[cfg(feature = "bson")]
+            DF::Bson => true,
+            #[cfg(feature = "urlencoded")]
+            DF::UrlEncoded => true,
+            #[cfg(feature = "ini")]
+            DF::Ini => true,
+            #[cfg(feature = "properties")]
+            DF::Properties => true,
+            #[cfg(feature = "dotenv")]
+            DF::DotEnv => true,
+            _ => false,
+        }
+    }
+
+    fn assert_round_trip<T>(
+        fmt: &crate::DeserializationFormat,
+        serializable: &T,
+    ) -> color_eyre::Result<()>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, fmt, serializable)?;
+        let deserialized: T = crate::deserialize_magically(sink.as_slice(), fmt)?;
+        assert_eq!(&deserialized, serializable);
 
         Ok(())
     }
@@ -6,11 +6,27 @@ pub mod fmt;
 
 pub mod fs;
 
-pub fn make_serializer<'w, W: Write + 'w, F: TryInto<crate::SerializationFormat>>(
+/// Makes a serializer of the format, which may have a compression layer, e.g. `"CBOR+zstd"`.
+///
+/// A compressed output is complete once the serializer is finished or dropped,
+/// see [`crate::MagicalSerializer::finish`].
+pub fn make_serializer<'w, W, F>(
     writer: W,
     format: F,
-) -> Result<crate::MagicalSerializer<'w>, F::Error> {
-    format.try_into().map(|fmt| fmt.serializer(writer))
+) -> color_eyre::Result<crate::MagicalSerializer<'w>>
+where
+    W: Write + 'w,
+    F: TryInto<crate::Compound<crate::SerializationFormat>>,
+    color_eyre::Report: From<F::Error>,
+{
+    let crate::Compound {
+        format,
+        compression,
+    } = format.try_into()?;
+    Ok(match compression {
+        Some(compression) => compression.serializer(writer, format)?,
+        None => format.serializer(writer),
+    })
 }
 
 /// Dynamically serialize any `serde::Serialize` object.
@@ -42,13 +58,13 @@ pub fn serialize_magically<'w, 'o, W, F, O>(
 ) -> color_eyre::Result<()>
 where
     W: Write + 'w,
-    F: TryInto<crate::SerializationFormat>,
+    F: TryInto<crate::Compound<crate::SerializationFormat>>,
     color_eyre::Report: From<F::Error>,
     O: serde::Serialize + ?Sized + 'o,
 {
     let mut serializer: crate::MagicalSerializer<'w> = make_serializer(writer, format)?;
     serializer.serialize(serializable)?;
-    Ok(())
+    serializer.finish()
 }
 
 #[cfg(test)]
@@ -37,6 +37,7 @@ pub enum SerializationFormat {
     #[strum(serialize = "JSON-compact")]
     CompactJson,
 
+    /// YAML, with every value after the first one written as a `---`-separated document.
     #[cfg(feature = "yaml")]
     #[strum(serialize = "YAML")]
     Yaml,
@@ -56,6 +57,140 @@ pub enum SerializationFormat {
     #[cfg(feature = "pickle")]
     #[strum(serialize = "Pickle")]
     Pickle,
+
+    #[cfg(feature = "toml")]
+    #[strum(serialize = "TOML-pretty", serialize = "TOML")]
+    PrettyToml,
+
+    #[cfg(feature = "toml")]
+    #[strum(serialize = "TOML-compact")]
+    CompactToml,
+
+    #[cfg(feature = "ron")]
+    #[strum(serialize = "RON-pretty", serialize = "RON")]
+    PrettyRon,
+
+    #[cfg(feature = "ron")]
+    #[strum(serialize = "RON-compact")]
+    CompactRon,
+
+    /// XML with the root element named after the serialized struct or enum.
+    ///
+    /// Other values have no name to use, so they're wrapped in a `<root>` element, with an
+    /// `<item>` element per item of a sequence. See [`SerializationFormat::xml_serializer`].
+    #[cfg(feature = "xml")]
+    #[strum(serialize = "XML")]
+    Xml,
+
+    #[cfg(feature = "csv")]
+    #[strum(serialize = "CSV")]
+    Csv,
+
+    #[cfg(feature = "csv")]
+    #[strum(serialize = "TSV")]
+    Tsv,
+
+    #[cfg(feature = "bson")]
+    #[strum(serialize = "BSON")]
+    Bson,
+
+    #[cfg(feature = "postcard")]
+    #[strum(serialize = "Postcard")]
+    Postcard,
+
+    /// Postcard framed with Consistent Overhead Byte Stuffing, ending with a zero byte.
+    #[cfg(feature = "postcard")]
+    #[strum(serialize = "Postcard-COBS")]
+    PostcardCobs,
+
+    #[cfg(feature = "flexbuffers")]
+    #[strum(serialize = "FlexBuffers")]
+    Flexbuffers,
+
+    /// S-expressions in the Scheme-like dialect, with `#t`/`#f` booleans and `#:keywords`.
+    #[cfg(feature = "sexpr")]
+    #[strum(serialize = "S-expression", serialize = "Lisp")]
+    Sexpr,
+
+    /// S-expressions in the Emacs Lisp dialect, with `t`/`nil` booleans and `:keywords`.
+    ///
+    /// Meant for Emacs, as it is read back in the Scheme-like dialect.
+    #[cfg(feature = "sexpr")]
+    #[strum(serialize = "S-expression-elisp", serialize = "Elisp")]
+    ElispSexpr,
+
+    /// `application/x-www-form-urlencoded` form bodies and query strings.
+    #[cfg(feature = "urlencoded")]
+    #[strum(serialize = "URL-encoded", serialize = "urlencoded")]
+    UrlEncoded,
+
+    /// JSON Lines (a.k.a. NDJSON), with every serialized value written as a compact JSON line.
+    ///
+    /// Thus, serializing to a file opened in the append mode adds a line to it.
+    #[cfg(feature = "jsonl")]
+    #[strum(serialize = "JSON-Lines", serialize = "NDJSON")]
+    JsonLines,
+
+    /// Bencode, as in BitTorrent metadata, which has no floats and encodes booleans as integers.
+    #[cfg(feature = "bencode")]
+    #[strum(serialize = "Bencode")]
+    Bencode,
+
+    /// Apple property list in the XML encoding.
+    #[cfg(feature = "plist")]
+    #[strum(serialize = "Plist-XML", serialize = "Plist")]
+    PlistXml,
+
+    /// Apple property list in the binary encoding.
+    #[cfg(feature = "plist")]
+    #[strum(serialize = "Plist-binary")]
+    PlistBinary,
+
+    /// INI, with nested structs or maps written as sections. See [`DeserializationFormat::Ini`].
+    #[cfg(feature = "ini")]
+    #[strum(serialize = "INI")]
+    Ini,
+
+    /// Java properties, with the keys of nested structs or maps joined by dots.
+    /// Keys can't hold dots themselves, as they wouldn't read back.
+    /// See [`DeserializationFormat::Properties`].
+    #[cfg(feature = "properties")]
+    #[strum(serialize = "Java-properties", serialize = "properties")]
+    Properties,
+
+    /// Dotenv variables, with the keys of nested structs joined by underscores and uppercased.
+    /// Maps don't nest and names can't collide once uppercased, as they wouldn't read back. See [`DeserializationFormat::DotEnv`].
+    #[cfg(feature = "dotenv")]
+    #[strum(serialize = "dotenv", serialize = ".env")]
+    DotEnv,
+
+    /// A Markdown file opened by YAML front matter between `---` lines, with an empty body.
+    /// See [`SerializationFormat::markdown_serializer`] to write a body as well.
+    #[cfg(feature = "front-matter")]
+    #[strum(serialize = "front-matter-YAML", serialize = "front-matter")]
+    YamlFrontMatter,
+
+    /// A Markdown file opened by TOML front matter between `+++` lines, with an empty body.
+    /// See [`SerializationFormat::markdown_serializer`] to write a body as well.
+    #[cfg(feature = "front-matter")]
+    #[strum(serialize = "front-matter-TOML")]
+    TomlFrontMatter,
+
+    /// Amazon Ion in the (pretty) text encoding.
+    #[cfg(feature = "ion")]
+    #[strum(serialize = "Ion-text", serialize = "Ion")]
+    IonText,
+
+    /// Amazon Ion in the binary encoding.
+    #[cfg(feature = "ion")]
+    #[strum(serialize = "Ion-binary")]
+    IonBinary,
+
+    /// Apache Avro object container file, which needs a schema to write.
+    /// See [`SerializationFormat::serializer_with_schema`].
+    #[cfg(feature = "avro")]
+    #[strum(serialize = "Avro")]
+    Avro,
 }
 
 impl SerializationFormat {
@@ -105,33 +240,310 @@ impl SerializationFormat {
                 m.set_sufix_for_writes(stop_opcode);
                 m
             }
+
+            #[cfg(feature = "toml")]
+            Self::PrettyToml => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    Ok(writer.write_all(toml::to_string_pretty(serializable)?.as_bytes())?)
+                })
+            }
+            #[cfg(feature = "toml")]
+            Self::CompactToml => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    Ok(writer.write_all(toml::to_string(serializable)?.as_bytes())?)
+                })
+            }
+
+            #[cfg(feature = "ron")]
+            Self::PrettyRon => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                let pretty_config = ron::ser::PrettyConfig::default();
+                Ok(ron::Options::default().to_io_writer_pretty(
+                    writer,
+                    serializable,
+                    pretty_config,
+                )?)
+            }),
+            #[cfg(feature = "ron")]
+            Self::CompactRon => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    Ok(ron::Options::default().to_io_writer(writer, serializable)?)
+                })
+            }
+
+            #[cfg(feature = "xml")]
+            Self::Xml => xml::serializer(writer, None),
+
+            #[cfg(feature = "csv")]
+            Self::Csv => {
+                crate::MagicalSerializer::new(records::RecordsSerializer::new(writer, b','))
+            }
+            #[cfg(feature = "csv")]
+            Self::Tsv => {
+                crate::MagicalSerializer::new(records::RecordsSerializer::new(writer, b'\t'))
+            }
+
+            #[cfg(feature = "bson")]
+            Self::Bson => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                Ok(bson::serialize_to_document(serializable)?.to_writer(writer)?)
+            }),
+
+            #[cfg(feature = "postcard")]
+            Self::Postcard => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                postcard::to_io(serializable, writer)?;
+                Ok(())
+            }),
+            #[cfg(feature = "postcard")]
+            Self::PostcardCobs => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    Ok(writer.write_all(&postcard::to_stdvec_cobs(serializable)?)?)
+                })
+            }
+
+            #[cfg(feature = "flexbuffers")]
+            Self::Flexbuffers => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    Ok(writer.write_all(&flexbuffers::to_vec(serializable)?)?)
+                })
+            }
+
+            #[cfg(feature = "sexpr")]
+            Self::Sexpr => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                Ok(serde_lexpr::to_writer(writer, &serializable)?)
+            }),
+            #[cfg(feature = "sexpr")]
+            Self::ElispSexpr => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    let print_options = serde_lexpr::print::Options::elisp();
+                    Ok(serde_lexpr::to_writer_custom(
+                        writer,
+                        &serializable,
+                        print_options,
+                    )?)
+                })
+            }
+
+            #[cfg(feature = "urlencoded")]
+            Self::UrlEncoded => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    use color_eyre::eyre::WrapErr as _;
+                    let form = serde_urlencoded::to_string(serializable)
+                        .wrap_err(crate::util::URL_ENCODED_NESTING_LIMITS)?;
+                    Ok(writer.write_all(form.as_bytes())?)
+                })
+            }
+
+            #[cfg(feature = "jsonl")]
+            Self::JsonLines => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                serde_json::to_writer(&mut *writer, serializable)?;
+                Ok(writer.write_all(b"\n")?)
+            }),
+
+            #[cfg(feature = "bencode")]
+            Self::Bencode => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                Ok(writer.write_all(&serde_bencode::to_bytes(&serializable)?)?)
+            }),
+
+            #[cfg(feature = "plist")]
+            Self::PlistXml => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                Ok(plist::to_writer_xml(writer, &serializable)?)
+            }),
+            #[cfg(feature = "plist")]
+            Self::PlistBinary => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    Ok(plist::to_writer_binary(writer, &serializable)?)
+                })
+            }
+
+            #[cfg(feature = "ini")]
+            Self::Ini => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                let ini = ini::to_ini(serializable)?;
+                Ok(ini.write_to_policy(writer, ::ini::EscapePolicy::Nothing)?)
+            }),
+
+            #[cfg(feature = "properties")]
+            Self::Properties => {
+                crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                    let mut properties_writer = java_properties::PropertiesWriter::new(writer);
+                    for (key, value) in flat::to_flat(serializable, "Java properties", ".", true)? {
+                        properties_writer.write(&key, &value)?;
+                    }
+                    Ok(properties_writer.finish()?)
+                })
+            }
+
+            #[cfg(feature = "dotenv")]
+            Self::DotEnv => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                let mut names = std::collections::HashSet::new();
+                for (name, value) in flat::to_flat(serializable, "dotenv", "_", false)? {
+                    let name = name.to_uppercase();
+                    color_eyre::eyre::ensure!(
+                        names.insert(name.clone()),
+                        "dotenv names are uppercased, so more than one key becomes {name}"
+                    );
+                    writeln!(writer, "{name}={}", flat::quote_dotenv_value(&value))?;
+                }
+                Ok(())
+            }),
+
+            #[cfg(feature = "front-matter")]
+            Self::YamlFrontMatter => {
+                front_matter_serializer(writer, crate::sede::front_matter::Delimiter::Yaml, "")
+            }
+            #[cfg(feature = "front-matter")]
+            Self::TomlFrontMatter => {
+                front_matter_serializer(writer, crate::sede::front_matter::Delimiter::Toml, "")
+            }
+
+            #[cfg(feature = "ion")]
+            Self::IonText => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                Ok(writer.write_all(ion_rs::serde::to_pretty(&serializable)?.as_bytes())?)
+            }),
+            #[cfg(feature = "ion")]
+            Self::IonBinary => crate::MagicalSerializer::from_fn(writer, |writer, serializable| {
+                Ok(writer.write_all(&ion_rs::serde::to_binary(&serializable)?)?)
+            }),
+
+            #[cfg(feature = "avro")]
+            Self::Avro => avro::serializer(writer, None),
+        }
+    }
+
+    /// Makes a serializer given a `schema`, e.g. the writer schema of Avro, in its JSON form.
+    ///
+    /// Fails for formats taking no schema, and for invalid schemas.
+    pub fn serializer_with_schema<'w, W: Write + 'w>(
+        self,
+        writer: W,
+        schema: &str,
+    ) -> color_eyre::Result<crate::MagicalSerializer<'w>> {
+        #[allow(clippy::match_single_binding)]
+        match self {
+            #[cfg(feature = "avro")]
+            Self::Avro => Ok(avro::serializer(
+                writer,
+                Some(apache_avro::Schema::parse_str(schema)?),
+            )),
+            _ => color_eyre::eyre::bail!("{self} takes no schema"),
         }
     }
 
     pub fn deserializer<'r, R: Read + 'r>(self, reader: R) -> crate::MagicalDeserializer<'r> {
         DeserializationFormat::from(self).deserializer(reader)
     }
+
+    /// Makes a serializer writing front matter followed by the body of the `markdown` document,
+    /// i.e. replacing its front matter, if any.
+    ///
+    /// Fails unless the format is [`SerializationFormat::YamlFrontMatter`]
+    /// or [`SerializationFormat::TomlFrontMatter`].
+    #[cfg(feature = "front-matter")]
+    pub fn markdown_serializer<'w, W: Write + 'w>(
+        self,
+        writer: W,
+        markdown: &'w str,
+    ) -> color_eyre::Result<crate::MagicalSerializer<'w>> {
+        use crate::sede::front_matter::{self, Delimiter};
+        let delimiter = match self {
+            Self::YamlFrontMatter => Delimiter::Yaml,
+            Self::TomlFrontMatter => Delimiter::Toml,
+            _ => color_eyre::eyre::bail!("{self} is not a front matter format"),
+        };
+        Ok(front_matter_serializer(
+            writer,
+            delimiter,
+            front_matter::body(markdown),
+        ))
+    }
+
+    /// Makes an XML serializer wrapping every value in a root element named `root_tag`,
+    /// including structs and enums, unlike [`SerializationFormat::Xml`].
+    ///
+    /// Each item of a sequence is written as an `<item>` element inside the root element.
+    ///
+    /// Fails unless the format is [`SerializationFormat::Xml`].
+    #[cfg(feature = "xml")]
+    pub fn xml_serializer<'w, W: Write + 'w>(
+        self,
+        writer: W,
+        root_tag: &'w str,
+    ) -> color_eyre::Result<crate::MagicalSerializer<'w>> {
+        match self {
+            Self::Xml => Ok(xml::serializer(writer, Some(root_tag))),
+            #[allow(unreachable_patterns)]
+            _ => color_eyre::eyre::bail!("{self} is not XML"),
+        }
+    }
+}
+
+/// Writes front matter in the format told by the delimiter, followed by the Markdown body.
+#[cfg(feature = "front-matter")]
+fn front_matter_serializer<'w, W: Write + 'w>(
+    writer: W,
+    delimiter: crate::sede::front_matter::Delimiter,
+    body: &'w str,
+) -> crate::MagicalSerializer<'w> {
+    use crate::sede::front_matter::Delimiter;
+    crate::MagicalSerializer::from_fn(writer, move |writer, serializable| {
+        let front_matter = match delimiter {
+            Delimiter::Yaml => serde_yaml::to_string(&serializable)?,
+            Delimiter::Toml => toml::to_string_pretty(serializable)?,
+        };
+        let line = delimiter.line();
+        Ok(write!(writer, "{line}\n{front_matter}{line}\n{body}")?)
+    })
 }
 
 // CRUD-C:
 
-impl From<DeserializationFormat> for SerializationFormat {
-    fn from(value: DeserializationFormat) -> Self {
-        (&value).into()
+impl TryFrom<DeserializationFormat> for SerializationFormat {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: DeserializationFormat) -> Result<Self, Self::Error> {
+        (&value).try_into()
     }
 }
 
-impl From<&DeserializationFormat> for SerializationFormat {
-    fn from(value: &DeserializationFormat) -> Self {
+/// Fails for read-only formats, which have no serializer.
+impl TryFrom<&DeserializationFormat> for SerializationFormat {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: &DeserializationFormat) -> Result<Self, Self::Error> {
         use DeserializationFormat as DF;
-        match value {
+        Ok(match value {
             DF::Json => Self::PrettyJson,
             DF::Yaml => Self::Yaml,
             DF::Cbor => Self::Cbor,
             DF::Rmp => Self::Rmp,
             DF::Bincode => Self::Bincode,
             DF::Pickle => Self::Pickle,
-        }
+            DF::Toml => Self::PrettyToml,
+            DF::Ron => Self::PrettyRon,
+            DF::Json5 => Self::PrettyJson,
+            DF::Jsonc => Self::PrettyJson,
+            DF::Xml => Self::Xml,
+            DF::Csv => Self::Csv,
+            DF::Tsv => Self::Tsv,
+            DF::Bson => Self::Bson,
+            DF::Postcard => Self::Postcard,
+            DF::PostcardCobs => Self::PostcardCobs,
+            DF::Flexbuffers => Self::Flexbuffers,
+            DF::Sexpr => Self::Sexpr,
+            DF::UrlEncoded => Self::UrlEncoded,
+            DF::JsonLines => Self::JsonLines,
+            DF::Bencode => Self::Bencode,
+            DF::Plist => Self::PlistXml,
+            DF::Ini => Self::Ini,
+            DF::Properties => Self::Properties,
+            DF::DotEnv => Self::DotEnv,
+            DF::FrontMatter => Self::YamlFrontMatter,
+            DF::Ion => Self::IonText,
+            DF::Avro => Self::Avro,
+            DF::Hocon => {
+                return Err(color_eyre::eyre::eyre!(
+                    "{value} is a read-only format, so it has no serializer"
+                ));
+            }
+        })
     }
 }
 
@@ -157,5 +569,17 @@ impl strum::EnumProperty for SerializationFormat {
     }
 }
 
+#[cfg(feature = "avro")]
+mod avro;
+#[cfg(any(feature = "properties", feature = "dotenv"))]
+mod flat;
+#[cfg(feature = "ini")]
+mod ini;
+#[cfg(any(feature = "ini", feature = "properties", feature = "dotenv"))]
+mod nodes;
+#[cfg(feature = "csv")]
+mod records;
 #[cfg(test)]
 mod test;
+#[cfg(feature = "xml")]
+mod xml;
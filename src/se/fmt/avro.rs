@@ -0,0 +1,31 @@
+//! Serialization as an Avro object container file.
+
+use std::io::Write;
+
+use apache_avro::{Schema, Writer};
+use color_eyre::eyre::eyre;
+
+/// Writes every serialized value as a record of the same container file,
+/// whose header tells the writer `schema`.
+pub fn serializer<'w, W: Write + 'w>(
+    writer: W,
+    schema: Option<Schema>,
+) -> crate::MagicalSerializer<'w> {
+    let marker: [u8; 16] = rand::random();
+    let mut has_header = false;
+    crate::MagicalSerializer::from_fn(writer, move |writer, serializable| {
+        let schema = schema.as_ref().ok_or_else(|| {
+            eyre!("Avro needs a schema to write, see `SerializationFormat::serializer_with_schema`")
+        })?;
+        let mut writer = Writer::builder()
+            .schema(schema)
+            .writer(writer)
+            .marker(marker)
+            .has_header(has_header)
+            .build();
+        writer.append_ser(serializable)?;
+        writer.flush()?;
+        has_header = true;
+        Ok(())
+    })
+}
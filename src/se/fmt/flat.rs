@@ -0,0 +1,89 @@
+//! Serialization of a struct or a map as flat keys and values, where the keys of nested structs
+//! and maps are joined with a separator (e.g. Java properties, dotenv).
+
+use std::borrow::Cow;
+
+use serde::{Serialize, de::value::Error, ser::Error as _};
+
+use super::nodes::{Node, to_table};
+
+/// Flattens the serialized value, joining the keys on the way to every plain value.
+///
+/// Unless `nested_maps`, only nested structs are flattened, see [`to_table`].
+/// Otherwise keys can't hold the separator, as they would read back as nested ones.
+pub fn to_flat<T: Serialize + ?Sized>(
+    serializable: &T,
+    format: &'static str,
+    separator: &str,
+    nested_maps: bool,
+) -> Result<Vec<(String, String)>, Error> {
+    let mut flat = Vec::new();
+    let flattening = Flattening {
+        format,
+        separator,
+        nested_maps,
+    };
+    flattening.flatten(
+        String::new(),
+        to_table(serializable, format, nested_maps)?,
+        &mut flat,
+    )?;
+    Ok(flat)
+}
+
+struct Flattening<'s> {
+    format: &'static str,
+    separator: &'s str,
+    nested_maps: bool,
+}
+
+impl Flattening<'_> {
+    fn flatten(
+        &self,
+        prefix: String,
+        table: Vec<(String, Node)>,
+        flat: &mut Vec<(String, String)>,
+    ) -> Result<(), Error> {
+        let separator = self.separator;
+        for (key, node) in table {
+            if self.nested_maps && key.contains(separator) {
+                return Err(Error::custom(format!(
+                    "{} keys can't hold `{separator}`, which separates nested keys, unlike {key:?}",
+                    self.format
+                )));
+            }
+            let key = if prefix.is_empty() {
+                key
+            } else {
+                format!("{prefix}{separator}{key}")
+            };
+            match node {
+                Node::Value(value) => flat.push((key, value)),
+                Node::Table(table) => self.flatten(key, table, flat)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Quotes the value of a dotenv variable, unless it's made of characters safe to leave unquoted.
+pub fn quote_dotenv_value(value: &str) -> Cow<'_, str> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_.,:/+@%=".contains(c);
+    if value.chars().all(is_safe) {
+        return Cow::Borrowed(value);
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' | '"' | '$' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
@@ -0,0 +1,73 @@
+//! Serialization of a struct or a map as an INI file, whose sections are nested structs or maps.
+
+use serde::{Serialize, de::value::Error, ser::Error as _};
+
+use super::nodes::{Node, to_table};
+
+const TOO_DEEP: &str =
+    "INI sections hold only plain values, so structs and maps nested in them cannot be represented";
+const MULTILINE: &str = "INI values and keys cannot span many lines";
+const PADDED: &str =
+    "INI values and keys cannot start or end with whitespace, which reads back trimmed";
+const RESERVED_IN_KEY: &str =
+    "INI keys cannot hold `=` or `:`, nor start with `[`, `;` or `#`, which read back differently";
+const RESERVED_IN_SECTION_NAME: &str = "INI section names cannot hold `]`";
+const QUOTED: &str = "INI values cannot start with a quote, which reads back removed";
+
+/// Lays out the serialized value as INI sections.
+pub fn to_ini<T: Serialize + ?Sized>(serializable: &T) -> Result<ini::Ini, Error> {
+    let mut ini = ini::Ini::new();
+    for (key, node) in to_table(serializable, "INI", true)? {
+        match node {
+            Node::Value(value) => {
+                ini.with_general_section()
+                    .set(checked_key(key)?, checked_value(value)?);
+            }
+            Node::Table(section) => {
+                let mut setter = ini.with_section(Some(checked_section_name(key)?));
+                for (key, node) in section {
+                    let Node::Value(value) = node else {
+                        return Err(Error::custom(TOO_DEEP));
+                    };
+                    setter.set(checked_key(key)?, checked_value(value)?);
+                }
+            }
+        }
+    }
+    Ok(ini)
+}
+
+fn checked_section_name(name: String) -> Result<String, Error> {
+    if name.contains(']') {
+        return Err(Error::custom(RESERVED_IN_SECTION_NAME));
+    }
+    unpadded(single_line(name)?)
+}
+
+fn checked_key(key: String) -> Result<String, Error> {
+    if key.contains(['=', ':']) || key.starts_with(['[', ';', '#']) {
+        return Err(Error::custom(RESERVED_IN_KEY));
+    }
+    unpadded(single_line(key)?)
+}
+
+fn checked_value(value: String) -> Result<String, Error> {
+    if value.starts_with(['"', '\'']) {
+        return Err(Error::custom(QUOTED));
+    }
+    unpadded(single_line(value)?)
+}
+
+fn single_line(text: String) -> Result<String, Error> {
+    if text.contains(['\n', '\r']) {
+        return Err(Error::custom(MULTILINE));
+    }
+    Ok(text)
+}
+
+fn unpadded(text: String) -> Result<String, Error> {
+    if text.trim() != text {
+        return Err(Error::custom(PADDED));
+    }
+    Ok(text)
+}
@@ -0,0 +1,295 @@
+//! Serialization of a struct or a map as a tree of textual values, for formats made of keys and
+//! values (e.g. INI, Java properties).
+
+use serde::{
+    Serialize,
+    de::value::Error,
+    ser::{Error as _, Impossible},
+};
+
+/// Lays out the serialized struct or map as entries of the named format.
+///
+/// Unless `nested_maps`, only the top level may be a map, whose values are then plain, and
+/// nested tables must be structs.
+pub fn to_table<T: Serialize + ?Sized>(
+    serializable: &T,
+    format: &'static str,
+    nested_maps: bool,
+) -> Result<Vec<(String, Node)>, Error> {
+    let top_level_serializer = NodeSerializer {
+        format,
+        nested_maps,
+        top_level: true,
+    };
+    match serializable.serialize(top_level_serializer)? {
+        Node::Table(entries) => Ok(entries),
+        Node::Value(_) => unreachable!("values should be rejected at the top level"),
+    }
+}
+
+/// A value as laid out in a format made of keys and values.
+pub enum Node {
+    Value(String),
+    /// The top level or a nested struct or map.
+    Table(Vec<(String, Node)>),
+}
+
+/// Serializes a value of the named format, either at the top level or nested in a table.
+#[derive(Clone, Copy)]
+struct NodeSerializer {
+    format: &'static str,
+    nested_maps: bool,
+    top_level: bool,
+}
+
+impl NodeSerializer {
+    fn value(self, value: impl ToString) -> Result<Node, Error> {
+        if self.top_level {
+            return Err(Error::custom(format!(
+                "{} expects a struct or a map at the top level",
+                self.format
+            )));
+        }
+        Ok(Node::Value(value.to_string()))
+    }
+
+    fn table(self) -> Result<SerializeTable, Error> {
+        Ok(SerializeTable {
+            format: self.format,
+            nested_maps: self.nested_maps,
+            entries: Vec::new(),
+            key: None,
+        })
+    }
+
+    fn unrepresentable(self, what: &str) -> Error {
+        Error::custom(format!(
+            "{} holds no {what}, so they cannot be represented",
+            self.format
+        ))
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Node, Error> {
+                self.value(value)
+            }
+        )*
+    };
+}
+
+impl serde::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = Error;
+    type SerializeSeq = Impossible<Node, Error>;
+    type SerializeTuple = Impossible<Node, Error>;
+    type SerializeTupleStruct = Impossible<Node, Error>;
+    type SerializeTupleVariant = Impossible<Node, Error>;
+    type SerializeMap = SerializeTable;
+    type SerializeStruct = SerializeTable;
+    type SerializeStructVariant = Impossible<Node, Error>;
+
+    serialize_display! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Node, Error> {
+        self.value(std::str::from_utf8(value).map_err(Error::custom)?)
+    }
+
+    /// Written as an empty value, which reads back as `None`.
+    fn serialize_none(self) -> Result<Node, Error> {
+        self.value("")
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node, Error> {
+        self.value("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Node, Error> {
+        self.value("")
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Node, Error> {
+        self.value(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Node, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> Result<Node, Error> {
+        Err(Error::custom(format!(
+            "{} can represent only unit enum variants, unlike `{variant}`",
+            self.format
+        )))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(self.unrepresentable("sequences"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(self.unrepresentable("sequences"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(self.unrepresentable("sequences"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(self.unrepresentable("sequences"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        if !self.top_level && !self.nested_maps {
+            return Err(self.unrepresentable("nested maps"));
+        }
+        self.table()
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.table()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::custom(format!(
+            "{} can represent only unit enum variants, unlike `{variant}`",
+            self.format
+        )))
+    }
+}
+
+/// Collects the entries of the top level or of a section.
+pub struct SerializeTable {
+    format: &'static str,
+    nested_maps: bool,
+    entries: Vec<(String, Node)>,
+    /// The key whose value is to be serialized next.
+    key: Option<String>,
+}
+
+impl SerializeTable {
+    fn nested_serializer(&self) -> NodeSerializer {
+        NodeSerializer {
+            format: self.format,
+            nested_maps: self.nested_maps,
+            top_level: false,
+        }
+    }
+
+    fn push(&mut self, key: String, value: &(impl Serialize + ?Sized)) -> Result<(), Error> {
+        let node = value.serialize(self.nested_serializer())?;
+        self.entries.push((key, node));
+        Ok(())
+    }
+}
+
+impl serde::ser::SerializeMap for SerializeTable {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        match key.serialize(self.nested_serializer()) {
+            Ok(Node::Value(key)) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(Error::custom(format!(
+                "{} keys must be plain values",
+                self.format
+            ))),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .expect("`serialize_key` should be called before `serialize_value`");
+        let node = value.serialize(self.nested_serializer())?;
+        if !self.nested_maps && matches!(node, Node::Table(_)) {
+            return Err(self
+                .nested_serializer()
+                .unrepresentable("structs or maps nested in maps"));
+        }
+        self.entries.push((key, node));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        Ok(Node::Table(self.entries))
+    }
+}
+
+impl serde::ser::SerializeStruct for SerializeTable {
+    type Ok = Node;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Node, Error> {
+        Ok(Node::Table(self.entries))
+    }
+}
@@ -0,0 +1,150 @@
+//! Serialization of a sequence of flat records as rows of a delimited text (CSV, TSV).
+
+use std::io::Write;
+
+use serde::ser::{Error as _, Impossible};
+
+const NOT_A_SEQUENCE: &str =
+    "delimited text expects a sequence of flat records (e.g. `Vec<Struct>`) at the top level";
+
+/// Writes every element of the top-level sequence as a single row.
+///
+/// The header is written before the first record that is a struct or a map.
+pub struct RecordsSerializer<W: Write> {
+    writer: csv::Writer<W>,
+}
+
+impl<W: Write> RecordsSerializer<W> {
+    pub fn new(writer: W, delimiter: u8) -> Self {
+        Self {
+            writer: csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(writer),
+        }
+    }
+}
+
+pub struct SerializeRecords<'s, W: Write> {
+    writer: &'s mut csv::Writer<W>,
+}
+
+impl<W: Write> serde::ser::SerializeSeq for SerializeRecords<'_, W> {
+    type Ok = ();
+    type Error = csv::Error;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(
+        &mut self,
+        record: &T,
+    ) -> Result<(), Self::Error> {
+        self.writer.serialize(record).map_err(|e| match e.kind() {
+            csv::ErrorKind::Serialize(msg) => {
+                csv::Error::custom(format!("records must be flat: {msg}"))
+            }
+            _ => e,
+        })
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+impl<W: Write> serde::ser::SerializeTuple for SerializeRecords<'_, W> {
+    type Ok = ();
+    type Error = csv::Error;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(
+        &mut self,
+        record: &T,
+    ) -> Result<(), Self::Error> {
+        serde::ser::SerializeSeq::serialize_element(self, record)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+macro_rules! reject {
+    ($($method:ident($($arg:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ok, Self::Error> {
+                Err(csv::Error::custom(NOT_A_SEQUENCE))
+            }
+        )*
+    };
+}
+
+impl<'s, W: Write> serde::Serializer for &'s mut RecordsSerializer<W> {
+    type Ok = ();
+    type Error = csv::Error;
+    type SerializeSeq = SerializeRecords<'s, W>;
+    type SerializeTuple = SerializeRecords<'s, W>;
+    type SerializeTupleStruct = Impossible<(), csv::Error>;
+    type SerializeTupleVariant = Impossible<(), csv::Error>;
+    type SerializeMap = Impossible<(), csv::Error>;
+    type SerializeStruct = Impossible<(), csv::Error>;
+    type SerializeStructVariant = Impossible<(), csv::Error>;
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeRecords {
+            writer: &mut self.writer,
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(SerializeRecords {
+            writer: &mut self.writer,
+        })
+    }
+
+    fn serialize_some<T: serde::Serialize + ?Sized>(self, _: &T) -> Result<(), Self::Error> {
+        Err(csv::Error::custom(NOT_A_SEQUENCE))
+    }
+
+    fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Err(csv::Error::custom(NOT_A_SEQUENCE))
+    }
+
+    fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Err(csv::Error::custom(NOT_A_SEQUENCE))
+    }
+
+    reject! {
+        serialize_bool(bool) -> ();
+        serialize_i8(i8) -> ();
+        serialize_i16(i16) -> ();
+        serialize_i32(i32) -> ();
+        serialize_i64(i64) -> ();
+        serialize_u8(u8) -> ();
+        serialize_u16(u16) -> ();
+        serialize_u32(u32) -> ();
+        serialize_u64(u64) -> ();
+        serialize_f32(f32) -> ();
+        serialize_f64(f64) -> ();
+        serialize_char(char) -> ();
+        serialize_str(&str) -> ();
+        serialize_bytes(&[u8]) -> ();
+        serialize_none() -> ();
+        serialize_unit() -> ();
+        serialize_unit_struct(&'static str) -> ();
+        serialize_unit_variant(&'static str, u32, &'static str) -> ();
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant;
+    }
+}
@@ -23,3 +23,552 @@ mod pickle {
         Ok(())
     }
 }
+
+#[cfg(feature = "ron")]
+mod ron {
+    #[derive(serde::Serialize)]
+    enum Shape {
+        Circle(f32),
+    }
+
+    #[test]
+    fn keeps_enum_variant_names() -> color_eyre::Result<()> {
+        let mut ron_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut ron_bytes, "RON-compact", &Shape::Circle(1.5))?;
+        assert_eq!(core::str::from_utf8(&ron_bytes)?, "Circle(1.5)");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "xml")]
+mod xml {
+    #[test]
+    fn wraps_sequence_in_configured_root() -> color_eyre::Result<()> {
+        let mut xml_bytes = Vec::<u8>::new();
+        let mut serializer =
+            crate::SerializationFormat::Xml.xml_serializer(&mut xml_bytes, "numbers")?;
+        serializer.serialize(&[1, 2])?;
+        drop(serializer);
+        assert_eq!(
+            core::str::from_utf8(&xml_bytes)?,
+            "<numbers><item>1</item><item>2</item></numbers>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wraps_other_values_in_configured_root_only() -> color_eyre::Result<()> {
+        #[derive(serde::Serialize)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut xml_bytes = Vec::<u8>::new();
+        let mut serializer =
+            crate::SerializationFormat::Xml.xml_serializer(&mut xml_bytes, "value")?;
+        serializer.serialize(&42)?;
+        serializer.serialize(&Point { x: 1, y: 2 })?;
+        drop(serializer);
+        assert_eq!(
+            core::str::from_utf8(&xml_bytes)?,
+            "<value>42</value><value><x>1</x><y>2</y></value>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sequences_and_primitives_read_back() -> color_eyre::Result<()> {
+        let mut xml_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut xml_bytes, "XML", &vec![1, 2])?;
+        assert_eq!(
+            core::str::from_utf8(&xml_bytes)?,
+            "<root><item>1</item><item>2</item></root>"
+        );
+        let deserialized: Vec<u8> = crate::deserialize_magically(xml_bytes.as_slice(), "XML")?;
+        assert_eq!(deserialized, [1, 2]);
+
+        let mut xml_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut xml_bytes, "XML", &Vec::<u8>::new())?;
+        let deserialized: Vec<u8> = crate::deserialize_magically(xml_bytes.as_slice(), "XML")?;
+        assert!(deserialized.is_empty());
+
+        let mut xml_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut xml_bytes, "XML", &(-1, 2.5, true))?;
+        let deserialized: (i8, f32, bool) =
+            crate::deserialize_magically(xml_bytes.as_slice(), "XML")?;
+        assert_eq!(deserialized, (-1, 2.5, true));
+
+        let mut xml_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut xml_bytes, "XML", &42)?;
+        assert_eq!(core::str::from_utf8(&xml_bytes)?, "<root>42</root>");
+        let deserialized: u8 = crate::deserialize_magically(xml_bytes.as_slice(), "XML")?;
+        assert_eq!(deserialized, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn records_in_configured_root_read_back() -> color_eyre::Result<()> {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let points = vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+        let mut xml_bytes = Vec::<u8>::new();
+        crate::SerializationFormat::Xml
+            .xml_serializer(&mut xml_bytes, "points")?
+            .serialize(&points)?;
+        let deserialized: Vec<Point> = crate::deserialize_magically(xml_bytes.as_slice(), "XML")?;
+        assert_eq!(deserialized, points);
+        assert!(
+            crate::SerializationFormat::PrettyJson
+                .xml_serializer(&mut Vec::new(), "points")
+                .is_err()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "csv")]
+mod csv {
+    #[derive(serde::Serialize)]
+    struct Record {
+        name: &'static str,
+        score: u32,
+    }
+
+    #[test]
+    fn writes_header_and_rows() -> color_eyre::Result<()> {
+        let records = [
+            Record {
+                name: "a",
+                score: 1,
+            },
+            Record {
+                name: "b",
+                score: 2,
+            },
+        ];
+        let mut csv_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut csv_bytes, "CSV", &records)?;
+        assert_eq!(core::str::from_utf8(&csv_bytes)?, "name,score\na,1\nb,2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_non_sequence() {
+        let record = Record {
+            name: "a",
+            score: 1,
+        };
+        let result = crate::serialize_magically(std::io::sink(), "CSV", &record);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("sequence of flat records")
+        );
+    }
+}
+
+#[cfg(feature = "postcard")]
+mod postcard {
+    use rand::Rng;
+
+    type Serializable = (i64, f32, bool);
+
+    #[test]
+    fn cobs_magic_should_work_like_static() -> color_eyre::Result<()> {
+        let mut rng = rand::rng();
+        let serializable: Serializable = Rng::random(&mut rng);
+
+        // Static
+        let static_cobs_bytes = postcard::to_stdvec_cobs(&serializable)?;
+
+        // Magic
+        let mut dynamic_cobs_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut dynamic_cobs_bytes, "Postcard-COBS", &serializable)?;
+
+        // Comparison
+        assert_eq!(dynamic_cobs_bytes, static_cobs_bytes);
+        assert_eq!(
+            dynamic_cobs_bytes.iter().position(|&b| b == 0),
+            Some(dynamic_cobs_bytes.len() - 1)
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sexpr")]
+mod sexpr {
+    #[test]
+    fn writes_booleans_per_dialect() -> color_eyre::Result<()> {
+        let mut lisp_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut lisp_bytes, "Lisp", &vec![true, false])?;
+        assert_eq!(core::str::from_utf8(&lisp_bytes)?, "(#t #f)");
+
+        let mut elisp_bytes = Vec::<u8>::new();
+        crate::serialize_magically(&mut elisp_bytes, "Elisp", &vec![true, false])?;
+        assert_eq!(core::str::from_utf8(&elisp_bytes)?, "(t nil)");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "jsonl")]
+mod jsonl {
+    #[test]
+    fn appends_line_per_serialization() -> color_eyre::Result<()> {
+        let path = std::env::temp_dir().join("sedes-appends-line-per-serialization.jsonl");
+        // Starts from scratch, as the classic write mode doesn't truncate the file.
+        let _ = std::fs::remove_file(&path);
+        crate::serialize_to_file(&path, "C", &[1, 2])?;
+        crate::serialize_to_file(&path, "A", &serde_json::json!({"three": 3}))?;
+        assert_eq!(std::fs::read_to_string(&path)?, "[1,2]\n{\"three\":3}\n");
+
+        let mut sink = Vec::<u8>::new();
+        let mut serializer = crate::make_serializer(&mut sink, "JSON-Lines")?;
+        serializer.serialize(&"four")?;
+        serializer.serialize(&5)?;
+        drop(serializer);
+        assert_eq!(sink, b"\"four\"\n5\n");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod yaml {
+    #[test]
+    fn separates_documents() -> color_eyre::Result<()> {
+        let mut sink = Vec::<u8>::new();
+        let mut serializer = crate::make_serializer(&mut sink, "YAML")?;
+        serializer.serialize_all([vec![1], vec![2, 3]])?;
+        drop(serializer);
+        assert_eq!(core::str::from_utf8(&sink)?, "- 1\n---\n- 2\n- 3\n");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "plist")]
+mod plist {
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn both_encodings_read_back_as_plist() -> color_eyre::Result<()> {
+        let info = BTreeMap::from([("CFBundleName", "Sedes"), ("CFBundleVersion", "1.0")]);
+        for (ser_fmt, magic) in [("Plist-XML", &b"<?xml"[..]), ("Plist-binary", b"bplist00")] {
+            let mut sink = Vec::<u8>::new();
+            crate::serialize_magically(&mut sink, ser_fmt, &info)?;
+            assert!(
+                sink.starts_with(magic),
+                "{ser_fmt} should start with {magic:?}"
+            );
+
+            let deserialized: BTreeMap<String, String> =
+                crate::deserialize_magically(sink.as_slice(), "Plist")?;
+            assert_eq!(deserialized["CFBundleName"], "Sedes");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ini")]
+mod ini {
+    use std::collections::BTreeMap;
+
+    #[derive(serde::Serialize)]
+    struct Config {
+        name: &'static str,
+        server: BTreeMap<&'static str, u16>,
+    }
+
+    #[test]
+    fn writes_nested_structs_as_sections() -> color_eyre::Result<()> {
+        let config = Config {
+            name: "legacy",
+            server: BTreeMap::from([("port", 8080)]),
+        };
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "INI", &config)?;
+        assert_eq!(
+            core::str::from_utf8(&sink)?,
+            "name=legacy\n\n[server]\nport=8080\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_deep_nesting_and_sequences() {
+        let deep = BTreeMap::from([("a", BTreeMap::from([("b", BTreeMap::from([("c", 1)]))]))]);
+        let error = crate::serialize_magically(Vec::<u8>::new(), "INI", &deep).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("nested in them cannot be represented")
+        );
+
+        let sequence = BTreeMap::from([("a", vec![1, 2])]);
+        let error = crate::serialize_magically(Vec::<u8>::new(), "INI", &sequence).unwrap_err();
+        assert!(error.to_string().contains("INI holds no sequences"));
+    }
+
+    #[test]
+    fn rejects_what_would_read_back_differently() {
+        let rejected = [
+            ("a=b", "c"),
+            ("a:b", "c"),
+            ("[a]", "c"),
+            ("#a", "c"),
+            (" a", "c"),
+            ("a", "  padded "),
+            ("a", "\"quoted\""),
+        ];
+        for (key, value) in rejected {
+            let table = BTreeMap::from([(key, value)]);
+            let serialized = crate::serialize_magically(Vec::<u8>::new(), "INI", &table);
+            assert!(serialized.is_err(), "{key:?} = {value:?} was written");
+        }
+    }
+
+    #[test]
+    fn keeps_reserved_characters_inside_values() -> color_eyre::Result<()> {
+        let table = BTreeMap::from([("a", "b=c; d # e"), ("f", "[g]")]);
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "INI", &table)?;
+        let deserialized: BTreeMap<String, String> =
+            crate::deserialize_magically(sink.as_slice(), "INI")?;
+        assert_eq!(
+            deserialized,
+            BTreeMap::from([
+                ("a".into(), "b=c; d # e".into()),
+                ("f".into(), "[g]".into())
+            ])
+        );
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "properties", feature = "dotenv"))]
+mod flat {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Config {
+        app_name: String,
+        server: Server,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Server {
+        port: u16,
+        context_path: Option<String>,
+    }
+
+    fn config() -> Config {
+        Config {
+            app_name: "my app".to_owned(),
+            server: Server {
+                port: 8080,
+                context_path: Some("/api".to_owned()),
+            },
+        }
+    }
+
+    #[cfg(feature = "properties")]
+    #[test]
+    fn properties_join_keys_with_dots() -> color_eyre::Result<()> {
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "properties", &config())?;
+        assert_eq!(
+            core::str::from_utf8(&sink)?,
+            "app_name=my\\ app\nserver.port=8080\nserver.context_path=/api\n"
+        );
+        let deserialized: Config = crate::deserialize_magically(sink.as_slice(), "properties")?;
+        assert_eq!(deserialized, config());
+        Ok(())
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv_joins_names_with_underscores() -> color_eyre::Result<()> {
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "dotenv", &config())?;
+        assert_eq!(
+            core::str::from_utf8(&sink)?,
+            "APP_NAME=\"my app\"\nSERVER_PORT=8080\nSERVER_CONTEXT_PATH=/api\n"
+        );
+        let deserialized: Config = crate::deserialize_magically(sink.as_slice(), "dotenv")?;
+        assert_eq!(deserialized, config());
+        Ok(())
+    }
+
+    #[cfg(feature = "properties")]
+    #[test]
+    fn properties_reject_keys_holding_dots() -> color_eyre::Result<()> {
+        let flat =
+            std::collections::BTreeMap::from([("logging_level".to_owned(), "DEBUG".to_owned())]);
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "properties", &flat)?;
+        let deserialized: std::collections::BTreeMap<String, String> =
+            crate::deserialize_magically(sink.as_slice(), "properties")?;
+        assert_eq!(deserialized, flat);
+
+        let dotted = std::collections::BTreeMap::from([("logging.level", "DEBUG")]);
+        assert!(crate::serialize_magically(&mut Vec::new(), "properties", &dotted).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv_rejects_names_colliding_once_uppercased() -> color_eyre::Result<()> {
+        let distinct = std::collections::BTreeMap::from([("a", 1), ("b", 2)]);
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "dotenv", &distinct)?;
+        let deserialized: std::collections::BTreeMap<String, u8> =
+            crate::deserialize_magically(sink.as_slice(), "dotenv")?;
+        assert_eq!(
+            deserialized,
+            [("A".to_owned(), 1), ("B".to_owned(), 2)].into()
+        );
+
+        let colliding = std::collections::BTreeMap::from([("a", 1), ("A", 2)]);
+        assert!(crate::serialize_magically(&mut Vec::new(), "dotenv", &colliding).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv_rejects_nested_maps() -> color_eyre::Result<()> {
+        let flat = serde_json::json!({"outer_inner": 1});
+        let mut sink = Vec::<u8>::new();
+        crate::serialize_magically(&mut sink, "dotenv", &flat)?;
+        let deserialized: serde_json::Value =
+            crate::deserialize_magically(sink.as_slice(), "dotenv")?;
+        assert_eq!(deserialized, serde_json::json!({"OUTER_INNER": 1}));
+
+        let nested = serde_json::json!({"outer": {"inner": 1}});
+        assert!(crate::serialize_magically(&mut Vec::new(), "dotenv", &nested).is_err());
+        let nested_in_map = std::collections::HashMap::from([("default", config())]);
+        assert!(crate::serialize_magically(&mut Vec::new(), "dotenv", &nested_in_map).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv_file_is_recognized() -> color_eyre::Result<()> {
+        let dir = std::env::temp_dir().join("sedes-dotenv-file-is-recognized");
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(".env");
+        std::fs::write(&path, "# Docker\nAPP_NAME='my app'\nSERVER_PORT=80\n")?;
+        let deserialized: Config = crate::deserialize_from_file(&path)?;
+        assert_eq!(deserialized.app_name, "my app");
+        assert_eq!(deserialized.server.port, 80);
+        assert_eq!(deserialized.server.context_path, None);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "front-matter")]
+mod front_matter {
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Post {
+        title: String,
+        draft: bool,
+    }
+
+    #[test]
+    fn markdown_serializer_replaces_front_matter() -> color_eyre::Result<()> {
+        let markdown = "---\ntitle: Draft\n---\n# Hello\n";
+        let mut sink = Vec::<u8>::new();
+        crate::SerializationFormat::YamlFrontMatter
+            .markdown_serializer(&mut sink, markdown)?
+            .serialize(&Post {
+                title: "Hello".to_owned(),
+                draft: false,
+            })?;
+        assert_eq!(
+            core::str::from_utf8(&sink)?,
+            "---\ntitle: Hello\ndraft: false\n---\n# Hello\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn file_keeps_body_and_front_matter_format() -> color_eyre::Result<()> {
+        let path = std::env::temp_dir().join("sedes-file-keeps-body-and-front-matter-format.md");
+        std::fs::write(
+            &path,
+            "+++\ntitle = \"A much longer draft title\"\ndraft = true\n+++\n# Hello\n",
+        )?;
+        let post = Post {
+            title: "Hello".to_owned(),
+            draft: false,
+        };
+        crate::serialize_to_file(&path, "W", &post)?;
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "+++\ntitle = \"Hello\"\ndraft = false\n+++\n# Hello\n"
+        );
+        let deserialized: Post = crate::deserialize_from_file(&path)?;
+        assert_eq!(deserialized, post);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ion")]
+mod ion {
+    #[test]
+    fn both_encodings_read_back_as_ion() -> color_eyre::Result<()> {
+        let serializable = (1_u8, "two".to_owned(), [3.5_f64]);
+        for ser_fmt in ["Ion-text", "Ion-binary"] {
+            let mut sink = Vec::<u8>::new();
+            crate::serialize_magically(&mut sink, ser_fmt, &serializable)?;
+            let deserialized: (u8, String, [f64; 1]) =
+                crate::deserialize_magically(sink.as_slice(), "Ion")?;
+            assert_eq!(deserialized, serializable);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "avro")]
+mod avro {
+    const SCHEMA: &str = r#"{"type": "record", "name": "Reading", "fields": [
+        {"name": "sensor", "type": "string"},
+        {"name": "value", "type": "double"}
+    ]}"#;
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Reading {
+        sensor: String,
+        value: f64,
+    }
+
+    #[test]
+    fn writes_records_into_one_container_file() -> color_eyre::Result<()> {
+        let readings = [("a", 1.5), ("b", -2.0)].map(|(sensor, value)| Reading {
+            sensor: sensor.to_owned(),
+            value,
+        });
+        let mut sink = Vec::<u8>::new();
+        crate::SerializationFormat::Avro
+            .serializer_with_schema(&mut sink, SCHEMA)?
+            .serialize_all(&readings)?;
+        let deserialized = crate::make_deserializer(sink.as_slice(), "Avro")?
+            .into_values::<Reading>()
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(deserialized, readings);
+        Ok(())
+    }
+
+    #[test]
+    fn needs_schema() {
+        let mut sink = Vec::<u8>::new();
+        assert!(crate::serialize_magically(&mut sink, "Avro", &1_i64).is_err());
+        assert!(
+            crate::SerializationFormat::Avro
+                .serializer_with_schema(&mut sink, "not a schema")
+                .is_err()
+        );
+    }
+}
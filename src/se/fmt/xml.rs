@@ -0,0 +1,208 @@
+//! Serialization as an XML document with a given root element.
+
+use std::io::Write;
+
+use serde::{
+    Serialize,
+    de::value::Error,
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+};
+
+/// The root element of values without a name of their own, unless another one is given.
+const DEFAULT_ROOT_TAG: &str = "root";
+
+/// Wraps every value in a root element named `root_tag`, writing each item of a sequence as an
+/// `<item>` element of its own.
+///
+/// Without `root_tag`, structs and enums are named after themselves, and other values wrapped
+/// in [`DEFAULT_ROOT_TAG`].
+pub fn serializer<'w, W: Write + 'w>(
+    writer: W,
+    root_tag: Option<&'w str>,
+) -> crate::MagicalSerializer<'w> {
+    crate::MagicalSerializer::from_fn(writer, move |writer, serializable| {
+        let document = match (Serialize::serialize(serializable, ShapeProbe)?, root_tag) {
+            (Shape::Named, None) => quick_xml::se::to_string(serializable)?,
+            (Shape::Sequence, root_tag) => quick_xml::se::to_string_with_root(
+                root_tag.unwrap_or(DEFAULT_ROOT_TAG),
+                &Items { item: serializable },
+            )?,
+            (_, root_tag) => quick_xml::se::to_string_with_root(
+                root_tag.unwrap_or(DEFAULT_ROOT_TAG),
+                serializable,
+            )?,
+        };
+        Ok(writer.write_all(document.as_bytes())?)
+    })
+}
+
+/// Holds a sequence, whose items quick-xml would otherwise write as many root elements.
+///
+/// Read back by the XML deserializer, which unwraps the `<item>` elements.
+#[derive(Serialize)]
+struct Items<'a, T: ?Sized> {
+    item: &'a T,
+}
+
+/// What decides the root element of a value.
+#[derive(Clone, Copy)]
+enum Shape {
+    Sequence,
+    /// A struct or an enum, which quick-xml names the root element after.
+    Named,
+    Unnamed,
+}
+
+/// Tells the shape of a value, without serializing what's inside.
+struct ShapeProbe;
+
+macro_rules! probe_shape {
+    ($($method:ident($($ty:ty),*) => $shape:ident;)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<Shape, Error> {
+                Ok(Shape::$shape)
+            }
+        )*
+    };
+}
+
+impl serde::Serializer for ShapeProbe {
+    type Ok = Shape;
+    type Error = Error;
+    type SerializeSeq = Skipped;
+    type SerializeTuple = Skipped;
+    type SerializeTupleStruct = Skipped;
+    type SerializeTupleVariant = Skipped;
+    type SerializeMap = Skipped;
+    type SerializeStruct = Skipped;
+    type SerializeStructVariant = Skipped;
+
+    probe_shape! {
+        serialize_bool(bool) => Unnamed;
+        serialize_i8(i8) => Unnamed;
+        serialize_i16(i16) => Unnamed;
+        serialize_i32(i32) => Unnamed;
+        serialize_i64(i64) => Unnamed;
+        serialize_i128(i128) => Unnamed;
+        serialize_u8(u8) => Unnamed;
+        serialize_u16(u16) => Unnamed;
+        serialize_u32(u32) => Unnamed;
+        serialize_u64(u64) => Unnamed;
+        serialize_u128(u128) => Unnamed;
+        serialize_f32(f32) => Unnamed;
+        serialize_f64(f64) => Unnamed;
+        serialize_char(char) => Unnamed;
+        serialize_str(&str) => Unnamed;
+        serialize_bytes(&[u8]) => Unnamed;
+        serialize_none() => Unnamed;
+        serialize_unit() => Unnamed;
+        serialize_unit_struct(&'static str) => Named;
+        serialize_unit_variant(&'static str, u32, &'static str) => Named;
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Shape, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Shape, Error> {
+        match value.serialize(self)? {
+            Shape::Sequence => Ok(Shape::Sequence),
+            Shape::Named | Shape::Unnamed => Ok(Shape::Named),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Shape, Error> {
+        Ok(Shape::Named)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Sequence))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Sequence))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Sequence))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Named))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Unnamed))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Named))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Skipped, Error> {
+        Ok(Skipped(Shape::Named))
+    }
+}
+
+/// Skips what's inside a compound value, telling its shape in the end.
+struct Skipped(Shape);
+
+macro_rules! skip_compound {
+    ($($trait:ident { $($method:ident($($arg:ident: $arg_ty:ty),*);)* })*) => {
+        $(
+            impl $trait for Skipped {
+                type Ok = Shape;
+                type Error = Error;
+
+                $(
+                    fn $method<T: Serialize + ?Sized>(
+                        &mut self,
+                        $($arg: $arg_ty,)*
+                        _value: &T,
+                    ) -> Result<(), Error> {
+                        Ok(())
+                    }
+                )*
+
+                fn end(self) -> Result<Shape, Error> {
+                    Ok(self.0)
+                }
+            }
+        )*
+    };
+}
+
+skip_compound! {
+    SerializeSeq { serialize_element(); }
+    SerializeTuple { serialize_element(); }
+    SerializeTupleStruct { serialize_field(); }
+    SerializeTupleVariant { serialize_field(); }
+    SerializeMap { serialize_key(); serialize_value(); }
+    SerializeStruct { serialize_field(_key: &'static str); }
+    SerializeStructVariant { serialize_field(_key: &'static str); }
+}
@@ -2,10 +2,17 @@ use std::{ffi::OsStr, path::Path};
 
 use color_eyre::eyre::{Context, OptionExt, eyre};
 
-use crate::{SedeFormat as _, SerializationFormat};
+use crate::{Compound, Compression, DeserializationFormat, SedeFormat as _, SerializationFormat};
 
 /// Serializes an object to a file deducting `[crate::SerializationFormat]` from file extension.
 ///
+/// A compression extension (e.g. `.json.gz`) is peeled off, and the output is compressed,
+/// see [`Compression`].
+///
+/// `write_mode` is a [`write_mode::WriteMode`] or its name, e.g. `"W"`, `"C"` or `"A"`.
+/// Since sedes 0.10, which takes `write_mode` 0.2, the former `UpdateExisting` (`"U"`) is
+/// `OverwriteExisting` (`"O"`).
+///
 /// # Examples
 ///
 /// Write to a temporary json file, then assert content.
@@ -40,17 +47,94 @@ where
         let write_mode: write_mode::WriteMode = write_mode.try_into()?;
 
         // Deduction of the serialization format.
+        let (compression, decompressed_path) = Compression::peel(path);
         let ser_fmt: SerializationFormat = (|| -> color_eyre::Result<SerializationFormat> {
-            let file_ext: &OsStr = path.extension().ok_or_eyre("File extension not found.")?;
-            SerializationFormat::from_file_ext_os(file_ext)
-                .ok_or_else(|| eyre!("File extension not recognized: {file_ext:?}"))
+            let file_ext: &OsStr =
+                crate::util::file_ext(decompressed_path).ok_or_eyre("File extension not found.")?;
+            let des_fmt = DeserializationFormat::from_file_ext_os(file_ext)
+                .ok_or_else(|| eyre!("File extension not recognized: {file_ext:?}"))?;
+            SerializationFormat::try_from(des_fmt)
         })()
         .context("Failed to deduce the serialization format from the file extension.")?;
 
+        #[cfg(feature = "front-matter")]
+        if ser_fmt == SerializationFormat::YamlFrontMatter {
+            color_eyre::eyre::ensure!(
+                compression.is_none(),
+                "Front matter cannot be written to a compressed Markdown file."
+            );
+            return serialize_front_matter_to_file(path, write_mode, serializable);
+        }
+
+        if let Some(compression) = compression {
+            color_eyre::eyre::ensure!(
+                compression.concatenates() || !appends(&write_mode),
+                "{compression} streams can't be appended to, as they wouldn't read back."
+            );
+        }
+
         // First IO op. – opening the file
-        let file = write_mode.std_open(path)?;
+        let mut file = write_mode.std_open(path)?;
+        let ser_fmt = Compound {
+            format: ser_fmt,
+            compression,
+        };
         // Last IO ops – reading and closing
-        crate::serialize_magically::<_, SerializationFormat, O>(file, ser_fmt, serializable)
+        crate::serialize_magically::<_, Compound<SerializationFormat>, O>(
+            &mut file,
+            ser_fmt,
+            serializable,
+        )?;
+        if compression.is_some() {
+            // Leftovers of a longer file would follow the compressed stream, unless truncated.
+            truncate_at_stream_position(&mut file)?;
+        }
+        Ok(())
     })()
     .wrap_err_with(|| format!("failed to serialize an object to a file {path:?}"))
 }
+
+/// Writes front matter to a Markdown file, preserving its body and the format of its front matter.
+#[cfg(feature = "front-matter")]
+fn serialize_front_matter_to_file<O: serde::Serialize + ?Sized>(
+    path: &Path,
+    write_mode: write_mode::WriteMode,
+    serializable: &O,
+) -> color_eyre::Result<()> {
+    use crate::sede::front_matter::{self, Delimiter};
+
+    if appends(&write_mode) {
+        color_eyre::eyre::bail!("Front matter cannot be appended to a Markdown file.");
+    }
+    let markdown = match std::fs::read_to_string(path) {
+        Ok(markdown) => markdown,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error).context("Failed to read the Markdown body."),
+    };
+    let ser_fmt = match front_matter::split(&markdown) {
+        Some(split) if split.delimiter == Delimiter::Toml => SerializationFormat::TomlFrontMatter,
+        _ => SerializationFormat::YamlFrontMatter,
+    };
+
+    let mut file = write_mode.std_open(path)?;
+    ser_fmt
+        .markdown_serializer(&mut file, &markdown)?
+        .serialize(serializable)?;
+    // The file is not truncated by every write mode, while the new content may be shorter.
+    truncate_at_stream_position(&mut file)
+}
+
+fn appends(write_mode: &write_mode::WriteMode) -> bool {
+    use write_mode::WriteMode;
+    matches!(
+        write_mode,
+        WriteMode::ClassicAppend | WriteMode::AppendToExisting
+    )
+}
+
+fn truncate_at_stream_position(file: &mut std::fs::File) -> color_eyre::Result<()> {
+    use std::io::Seek as _;
+    let len = file.stream_position()?;
+    file.set_len(len)?;
+    Ok(())
+}
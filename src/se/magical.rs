@@ -1,17 +1,32 @@
-use std::{borrow::BorrowMut, cell::OnceCell, error::Error, io::Write, ptr::NonNull};
+use std::{borrow::BorrowMut, cell::OnceCell, io::Write, ptr::NonNull};
 
 type SeizedWriterHandle<'w> = crate::util::RcRfDynWriter<'w>;
 
+type SerializeFn<'w> = Box<dyn FnMut(&dyn erased_serde::Serialize) -> color_eyre::Result<()> + 'w>;
+
+type FinishFn<'w> = Box<dyn FnOnce() -> color_eyre::Result<()> + 'w>;
+
 pub struct MagicalSerializer<'w> {
     prefix_for_writes: &'static [u8],
     sufix_for_writes: &'static [u8],
     writer: OnceCell<SeizedWriterHandle<'w>>,
-    /// Don't R/W from this address untill dropping the "dependant",
-    /// which uniquely borrows the addressed value.
-    boxed_dependency: *mut (dyn crate::util::Something + 'w),
-    /// Should be dropped first. Should not be taken out of the field.
-    erased_dependant: NonNull<dyn erased_serde::Serializer + 'w>,
+    backend: Backend<'w>,
+    /// Should be dropped after the backend, which may still write on drop.
+    finisher: Finisher<'w>,
 }
+
+enum Backend<'w> {
+    Erased {
+        /// Don't R/W from this address untill dropping the "dependant",
+        /// which uniquely borrows the addressed value.
+        boxed_dependency: *mut (dyn crate::util::Something + 'w),
+        /// Should be dropped first. Should not be taken out of the field.
+        erased_dependant: NonNull<dyn erased_serde::Serializer + 'w>,
+    },
+    /// Serializes every value afresh.
+    Deferred(SerializeFn<'w>),
+}
+
 impl<'w> MagicalSerializer<'w> {
     // CRUD-C: Constructors
 
@@ -19,34 +34,48 @@ impl<'w> MagicalSerializer<'w> {
     where
         T: serde::Serializer + 'w,
     {
-        Self {
-            prefix_for_writes: b"",
-            sufix_for_writes: b"",
-            writer: OnceCell::new(),
+        Self::with_backend(Backend::Erased {
             boxed_dependency: Box::leak(Box::new(())),
             erased_dependant: unsafe {
                 NonNull::new_unchecked(Box::leak(Box::new(<dyn erased_serde::Serializer>::erase(
                     typed_serializer,
                 ))))
             },
-        }
+        })
     }
-    pub fn new<T>(typed_serializer: T) -> Self
+    /// Unlike [`MagicalSerializer::from_direct_impl`], can serialize many values in turn.
+    pub fn new<T>(mut typed_serializer: T) -> Self
     where
         T: 'w,
-        &'w mut T: serde::Serializer,
+        for<'s> &'s mut T: serde::Serializer,
     {
-        let boxed_dependency: *mut T = Box::leak(Box::new(typed_serializer));
+        Self::with_backend(Backend::Deferred(Box::new(move |serializable| {
+            Ok(
+                serializable.erased_serialize(&mut <dyn erased_serde::Serializer>::erase(
+                    &mut typed_serializer,
+                ))?,
+            )
+        })))
+    }
+    /// Wraps a function that serializes a whole value into the writer by itself.
+    ///
+    /// Meant for formats that only expose `to_string`/`to_vec`-like functions.
+    pub fn from_fn<W, F>(mut writer: W, mut serialize_fn: F) -> Self
+    where
+        W: Write + 'w,
+        F: FnMut(&mut W, &dyn erased_serde::Serialize) -> color_eyre::Result<()> + 'w,
+    {
+        Self::with_backend(Backend::Deferred(Box::new(move |serializable| {
+            serialize_fn(&mut writer, serializable)
+        })))
+    }
+    fn with_backend(backend: Backend<'w>) -> Self {
         Self {
             prefix_for_writes: b"",
             sufix_for_writes: b"",
             writer: OnceCell::new(),
-            boxed_dependency,
-            erased_dependant: unsafe {
-                NonNull::new_unchecked(Box::leak(Box::new(<dyn erased_serde::Serializer>::erase(
-                    &mut *boxed_dependency,
-                ))))
-            },
+            backend,
+            finisher: Finisher(None),
         }
     }
     // CRUD-R: Read settings
@@ -63,17 +92,48 @@ impl<'w> MagicalSerializer<'w> {
     pub fn set_sufix_for_writes(&mut self, bytes: &'static [u8]) {
         self.sufix_for_writes = bytes;
     }
+    /// Makes the serializer call `finish_fn` once done with the writer, e.g. to end a compressed stream.
+    ///
+    /// It's called by [`MagicalSerializer::finish`], or on drop, which ignores its errors.
+    pub fn set_finish_fn<F>(&mut self, finish_fn: F)
+    where
+        F: FnOnce() -> color_eyre::Result<()> + 'w,
+    {
+        self.finisher = Finisher(Some(Box::new(finish_fn)));
+    }
 
     // CRUD-U: Write instructions
-    pub fn serialize<'o, O: serde::Serialize + ?Sized>(
+    pub fn serialize<O: serde::Serialize + ?Sized>(
         &mut self,
-        serializable: &'o O,
+        serializable: &O,
     ) -> color_eyre::Result<()> {
         self.write_prefix()?;
         self.serialize_austerely(serializable)?;
         self.write_sufix()?;
         Ok(())
     }
+    /// Serializes every value in turn, e.g. as documents of a YAML stream or lines of JSON Lines.
+    pub fn serialize_all<I>(&mut self, serializables: I) -> color_eyre::Result<()>
+    where
+        I: IntoIterator<Item: serde::Serialize>,
+    {
+        for serializable in serializables {
+            self.serialize(&serializable)?;
+        }
+        Ok(())
+    }
+    /// Finishes the output (e.g. ends a compressed stream), reporting errors that drop would ignore.
+    pub fn finish(self) -> color_eyre::Result<()> {
+        let Self {
+            writer,
+            backend,
+            mut finisher,
+            ..
+        } = self;
+        drop(backend);
+        drop(writer);
+        finisher.0.take().map_or(Ok(()), |finish_fn| finish_fn())
+    }
     fn write_prefix(&mut self) -> std::io::Result<usize> {
         let prefix = self.prefix_for_writes();
         if let Some(writer) = self.writer.get_mut() {
@@ -94,19 +154,29 @@ impl<'w> MagicalSerializer<'w> {
             panic!()
         }
     }
-    fn serialize_austerely<'o, O: serde::Serialize + ?Sized>(
+    fn serialize_austerely<O: serde::Serialize + ?Sized>(
         &mut self,
-        serializable: &'o O,
-    ) -> Result<(), impl Error + 'static> {
-        erased_serde::Serialize::erased_serialize(serializable, unsafe {
-            // We trust the called function to not take the value out of the field.
-            // We know this is the only accessor of all chained serializer's dependencies.
-            self.erased_dependant.as_mut()
-        })
+        serializable: &O,
+    ) -> color_eyre::Result<()> {
+        match &mut self.backend {
+            Backend::Erased {
+                erased_dependant, ..
+            } => Ok(erased_serde::Serialize::erased_serialize(
+                serializable,
+                unsafe {
+                    // We trust the called function to not take the value out of the field.
+                    // We know this is the only accessor of all chained serializer's dependencies.
+                    erased_dependant.as_mut()
+                },
+            )?),
+            Backend::Deferred(serialize_fn) => serialize_fn(&serializable),
+        }
     }
 }
 impl<'w> MagicalSerializer<'w> {
-    /// Safety: Must seize the writer that is really the one,
+    /// # Safety
+    ///
+    /// Must seize the writer that is really the one,
     /// that is being used under the hood of serializer(s).
     pub unsafe fn with_seized_writer<'seized_w: 'w>(
         self,
@@ -124,11 +194,28 @@ impl<'w> MagicalSerializer<'w> {
         prolonged_self
     }
 }
-impl<'w> Drop for MagicalSerializer<'w> {
+impl<'w> Drop for Backend<'w> {
+    fn drop(&mut self) {
+        if let Backend::Erased {
+            boxed_dependency,
+            erased_dependant,
+        } = self
+        {
+            unsafe {
+                drop(Box::from_raw(erased_dependant.as_ptr()));
+                drop(Box::from_raw(*boxed_dependency));
+            }
+        }
+    }
+}
+
+/// Calls the finishing function on drop, unless it was called already.
+struct Finisher<'w>(Option<FinishFn<'w>>);
+
+impl Drop for Finisher<'_> {
     fn drop(&mut self) {
-        unsafe {
-            drop(Box::from_raw(self.erased_dependant.as_ptr()));
-            drop(Box::from_raw(self.boxed_dependency));
+        if let Some(finish_fn) = self.0.take() {
+            let _ = finish_fn();
         }
     }
 }
@@ -1 +1,5 @@
+pub mod compound;
+pub mod compression;
 pub mod fmt;
+#[cfg(feature = "front-matter")]
+pub(crate) mod front_matter;
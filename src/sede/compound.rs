@@ -0,0 +1,203 @@
+//! (De)serialization formats with a compression layer, e.g. `"CBOR+zstd"`.
+
+use std::str::FromStr;
+
+use crate::{Compression, DeserializationFormat, SerializationFormat};
+
+/// A (de)serialization format, optionally with a compression layer.
+///
+/// Made from a bare format, from a `(format, Compression)` pair,
+/// or parsed from a descriptor such as `"CBOR+zstd"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Compound<F> {
+    pub format: F,
+    pub compression: Option<Compression>,
+}
+
+impl<F> From<F> for Compound<F> {
+    fn from(format: F) -> Self {
+        Self {
+            format,
+            compression: None,
+        }
+    }
+}
+
+impl<F> From<(F, Compression)> for Compound<F> {
+    fn from((format, compression): (F, Compression)) -> Self {
+        Self {
+            format,
+            compression: Some(compression),
+        }
+    }
+}
+
+/// Parses e.g. `"CBOR+zstd"`, or a bare format.
+impl<F> FromStr for Compound<F>
+where
+    F: FromStr,
+    color_eyre::Report: From<F::Err>,
+{
+    type Err = color_eyre::Report;
+
+    fn from_str(descriptor: &str) -> Result<Self, Self::Err> {
+        let compressed = descriptor
+            .rsplit_once('+')
+            .and_then(|(format, compression)| {
+                Some((format, compression.trim().parse::<Compression>().ok()?))
+            });
+        match compressed {
+            Some((format, compression)) => Ok((format.trim().parse()?, compression).into()),
+            None => Ok(descriptor.parse::<F>()?.into()),
+        }
+    }
+}
+
+impl<F: std::fmt::Display> std::fmt::Display for Compound<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.compression {
+            Some(compression) => write!(f, "{}+{compression}", self.format),
+            None => self.format.fmt(f),
+        }
+    }
+}
+
+/// Converts whatever converts into the format (e.g. the other kind of formats).
+macro_rules! impl_try_from_formats {
+    ($format:ty: $($source:ty),* $(,)?) => {
+        $(
+            impl TryFrom<$source> for Compound<$format> {
+                type Error = color_eyre::Report;
+
+                fn try_from(source: $source) -> Result<Self, Self::Error> {
+                    Ok(<$format>::try_from(source)?.into())
+                }
+            }
+        )*
+
+        impl TryFrom<&str> for Compound<$format> {
+            type Error = color_eyre::Report;
+
+            fn try_from(descriptor: &str) -> Result<Self, Self::Error> {
+                descriptor.parse()
+            }
+        }
+    };
+}
+
+impl_try_from_formats!(SerializationFormat: &SerializationFormat, DeserializationFormat, &DeserializationFormat);
+impl_try_from_formats!(DeserializationFormat: &DeserializationFormat, SerializationFormat, &SerializationFormat);
+
+#[cfg(test)]
+mod test {
+    use super::Compound;
+    use crate::{Compression, SerializationFormat};
+
+    #[cfg(all(feature = "cbor", feature = "zstd"))]
+    #[test]
+    fn parses_compressed_descriptor() -> color_eyre::Result<()> {
+        let compound: Compound<SerializationFormat> = "CBOR+zstd".parse()?;
+        assert_eq!(
+            compound,
+            (SerializationFormat::Cbor, Compression::Zstd).into()
+        );
+        assert_eq!(compound.to_string(), "CBOR+zstd");
+        let compound: Compound<SerializationFormat> = "CBOR".parse()?;
+        assert_eq!(compound, SerializationFormat::Cbor.into());
+        assert!("CBOR+rar".parse::<Compound<SerializationFormat>>().is_err());
+        Ok(())
+    }
+
+    #[cfg(all(feature = "cbor", feature = "compression"))]
+    #[test]
+    fn payloads_round_trip_through_every_compression() -> color_eyre::Result<()> {
+        use strum::VariantArray as _;
+
+        let payload = vec![(1_u8, "payload".to_owned()); 64];
+        for &compression in Compression::VARIANTS {
+            let mut sink = Vec::<u8>::new();
+            crate::serialize_magically(
+                &mut sink,
+                format!("CBOR+{compression}").as_str(),
+                &payload,
+            )?;
+            assert!(sink.len() < serde_cbor::to_vec(&payload)?.len());
+            let deserialized: Vec<(u8, String)> = crate::deserialize_magically(
+                sink.as_slice(),
+                (crate::DeserializationFormat::Cbor, compression),
+            )?;
+            assert_eq!(deserialized, payload, "{compression}");
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "jsonl", feature = "compression"))]
+    #[test]
+    fn values_round_trip_through_every_compression() -> color_eyre::Result<()> {
+        use strum::VariantArray as _;
+
+        for &compression in Compression::VARIANTS {
+            let mut sink = Vec::<u8>::new();
+            let mut serializer =
+                crate::make_serializer(&mut sink, format!("JSON-Lines+{compression}").as_str())?;
+            serializer.serialize_all([1, 2, 3])?;
+            serializer.finish()?;
+            let values = crate::make_deserializer(
+                sink.as_slice(),
+                (crate::DeserializationFormat::JsonLines, compression),
+            )?
+            .into_values::<u8>()
+            .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(values, [1, 2, 3], "{compression}");
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "yaml", feature = "compression"))]
+    #[test]
+    fn yaml_documents_round_trip_through_every_compression() -> color_eyre::Result<()> {
+        use strum::VariantArray as _;
+
+        for &compression in Compression::VARIANTS {
+            let mut sink = Vec::<u8>::new();
+            let mut serializer =
+                crate::make_serializer(&mut sink, format!("YAML+{compression}").as_str())?;
+            serializer.serialize_all([vec![1], vec![2, 3]])?;
+            // Dropping ends the compressed stream too.
+            drop(serializer);
+            let documents = crate::make_deserializer(
+                sink.as_slice(),
+                (crate::DeserializationFormat::Yaml, compression),
+            )?
+            .into_values::<Vec<u8>>()
+            .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(documents, [vec![1], vec![2, 3]], "{compression}");
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "csv", feature = "compression"))]
+    #[test]
+    fn csv_records_round_trip_through_every_compression() -> color_eyre::Result<()> {
+        use strum::VariantArray as _;
+
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Record {
+            a: u8,
+        }
+
+        for &compression in Compression::VARIANTS {
+            let mut sink = Vec::<u8>::new();
+            let mut serializer =
+                crate::make_serializer(&mut sink, format!("CSV+{compression}").as_str())?;
+            serializer.serialize_all([vec![Record { a: 1 }], vec![Record { a: 2 }]])?;
+            serializer.finish()?;
+            let records: Vec<Record> = crate::deserialize_magically(
+                sink.as_slice(),
+                (crate::DeserializationFormat::Csv, compression),
+            )?;
+            assert_eq!(records, [Record { a: 1 }, Record { a: 2 }], "{compression}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,380 @@
+//! Compression layers around (de)serialization formats, e.g. gzip in `state.json.gz`.
+
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+use strum::{EnumProperty as _, VariantArray as _};
+
+/// A compression layer, told by the outermost file extension of compound ones (e.g. `.json.gz`).
+#[derive(
+    // CRUD-C: Constructors
+    serde::Deserialize,
+    Clone,
+    Copy,
+    strum::EnumString,
+    // CRUD-R: Properties
+    strum::VariantArray,
+    strum::VariantNames,
+    strum::EnumIs,
+    strum::EnumProperty,
+    // CRUD-R: Displayers
+    strum::IntoStaticStr,
+    strum::Display,
+    Debug,
+    // CRUD-R: Equivalence
+    PartialEq,
+    Eq,
+    Hash,
+    // CRUD-R: Misc
+    serde::Serialize,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Compression {
+    #[cfg(feature = "gzip")]
+    #[strum(serialize = "gzip", serialize = "gz", props(file_ext = "gz"))]
+    Gzip,
+
+    #[cfg(feature = "zstd")]
+    #[strum(serialize = "zstd", serialize = "zst", props(file_ext = "zst"))]
+    Zstd,
+
+    #[cfg(feature = "xz")]
+    #[strum(serialize = "xz", props(file_ext = "xz"))]
+    Xz,
+
+    #[cfg(feature = "bzip2")]
+    #[strum(serialize = "bzip2", serialize = "bz2", props(file_ext = "bz2"))]
+    Bzip2,
+
+    /// The LZ4 frame format.
+    #[cfg(feature = "lz4")]
+    #[strum(serialize = "lz4", props(file_ext = "lz4"))]
+    Lz4,
+
+    #[cfg(feature = "brotli")]
+    #[strum(serialize = "brotli", serialize = "br", props(file_ext = "br"))]
+    Brotli,
+}
+
+impl Compression {
+    pub fn file_ext(self) -> &'static str {
+        self.get_str("file_ext")
+            .expect("every compression should have a file extension")
+    }
+
+    pub fn from_file_ext(file_ext: &str) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .copied()
+            .find(|compression| compression.file_ext() == file_ext)
+    }
+
+    /// Splits the compression extension, if any, off the path,
+    /// e.g. `state.json.gz` into gzip and `state.json`.
+    pub fn peel(path: &Path) -> (Option<Self>, &Path) {
+        let compression = crate::util::file_ext(path)
+            .and_then(|file_ext| file_ext.to_str())
+            .and_then(Self::from_file_ext);
+        match (compression, path.file_stem()) {
+            (Some(compression), Some(file_stem)) => (Some(compression), Path::new(file_stem)),
+            _ => (None, path),
+        }
+    }
+
+    /// Whether streams appended to one another decompress as one, so compressed files can be appended to.
+    pub fn concatenates(self) -> bool {
+        match self {
+            #[cfg(feature = "brotli")]
+            Self::Brotli => false,
+            #[allow(unreachable_patterns)]
+            _ => true,
+        }
+    }
+
+    /// Decompresses the reader, reading concatenated streams (e.g. appended gzip members) too,
+    /// see [`Compression::concatenates`].
+    ///
+    /// Data after the last stream is an error rather than ignored.
+    pub fn decoder<'r, R: Read + 'r>(self, reader: R) -> std::io::Result<Box<dyn Read + 'r>> {
+        #[allow(unreachable_code)]
+        Ok(match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Box::new(zstd::Decoder::new(reader)?),
+            #[cfg(feature = "xz")]
+            Self::Xz => Box::new(liblzma::read::XzDecoder::new_multi_decoder(reader)),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Box::new(Lz4Decoder(lz4_flex::frame::FrameDecoder::new(
+                std::io::BufReader::new(reader),
+            ))),
+            #[cfg(feature = "brotli")]
+            Self::Brotli => Box::new(BrotliDecoder(brotli::Decompressor::new(
+                reader,
+                BUFFER_SIZE,
+            ))),
+        })
+    }
+
+    /// Makes a serializer of the format writing every serialized value into one compressed stream.
+    ///
+    /// The stream is ended by [`crate::MagicalSerializer::finish`], or else on drop.
+    pub fn serializer<'w, W: Write + 'w>(
+        self,
+        writer: W,
+        format: crate::SerializationFormat,
+    ) -> std::io::Result<crate::MagicalSerializer<'w>> {
+        let encoder = crate::util::RcRfWriter::from(self.encoder(writer)?);
+        let mut serializer = format.serializer(encoder.clone());
+        serializer.set_finish_fn(move || {
+            let encoder = encoder.try_into_inner().map_err(|_| {
+                color_eyre::eyre::eyre!("The compressed stream is still written to.")
+            })?;
+            Ok(encoder.finish()?)
+        });
+        Ok(serializer)
+    }
+
+    /// Compresses into the writer, which is complete only once the encoder is finished.
+    pub fn encoder<'w, W: Write + 'w>(self, writer: W) -> std::io::Result<Encoder<'w>> {
+        #[allow(unreachable_code)]
+        Ok(Encoder(match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => Box::new(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Box::new(zstd::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)?),
+            #[cfg(feature = "xz")]
+            Self::Xz => Box::new(liblzma::write::XzEncoder::new(writer, 6)),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => Box::new(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::default(),
+            )),
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Box::new(lz4_flex::frame::FrameEncoder::new(writer)),
+            #[cfg(feature = "brotli")]
+            Self::Brotli => Box::new(brotli::CompressorWriter::new(
+                writer,
+                BUFFER_SIZE,
+                BROTLI_QUALITY,
+                BROTLI_LG_WINDOW_SIZE,
+            )),
+        }))
+    }
+}
+
+#[cfg(feature = "brotli")]
+const BUFFER_SIZE: usize = 4096;
+#[cfg(feature = "brotli")]
+const BROTLI_QUALITY: u32 = 11;
+#[cfg(feature = "brotli")]
+const BROTLI_LG_WINDOW_SIZE: u32 = 22;
+
+/// Reads every frame of the input, while the frame decoder stops at the end of each one.
+#[cfg(feature = "lz4")]
+struct Lz4Decoder<R: Read>(lz4_flex::frame::FrameDecoder<std::io::BufReader<R>>);
+
+#[cfg(feature = "lz4")]
+impl<R: Read> Read for Lz4Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::BufRead as _;
+        loop {
+            let read = self.0.read(buf)?;
+            // The frame decoder reads frames exactly, so the input past them is still buffered.
+            if read != 0 || buf.is_empty() || self.0.get_mut().fill_buf()?.is_empty() {
+                return Ok(read);
+            }
+        }
+    }
+}
+
+/// Reads a brotli stream, failing on data after its end, e.g. another stream appended to it.
+#[cfg(feature = "brotli")]
+struct BrotliDecoder<R: Read>(brotli::Decompressor<R>);
+
+#[cfg(feature = "brotli")]
+impl<R: Read> Read for BrotliDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.0.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            // Reading past the end fails on data the decompressor has buffered already.
+            let trailing = self.0.read(&mut [0])? + self.0.get_mut().read(&mut [0])?;
+            if trailing != 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "data follows the end of the brotli stream",
+                ));
+            }
+        }
+        Ok(read)
+    }
+}
+
+/// A compressing writer, which writes the end of the compressed stream when finished.
+pub struct Encoder<'w>(Box<dyn FinishWrite + 'w>);
+
+impl Encoder<'_> {
+    /// Writes the end of the compressed stream, without which it can't be decompressed.
+    pub fn finish(self) -> std::io::Result<()> {
+        self.0.finish()
+    }
+}
+
+impl Write for Encoder<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+trait FinishWrite: Write {
+    fn finish(self: Box<Self>) -> std::io::Result<()>;
+}
+
+macro_rules! impl_finish_write {
+    ($($feature:literal => $encoder:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = $feature)]
+            impl<W: Write> FinishWrite for $encoder {
+                fn finish(self: Box<Self>) -> std::io::Result<()> {
+                    (*self).finish()?.flush()
+                }
+            }
+        )*
+    };
+}
+
+impl_finish_write! {
+    "gzip" => flate2::write::GzEncoder<W>,
+    "zstd" => zstd::Encoder<'static, W>,
+    "xz" => liblzma::write::XzEncoder<W>,
+    "bzip2" => bzip2::write::BzEncoder<W>,
+}
+
+#[cfg(feature = "lz4")]
+impl<W: Write> FinishWrite for lz4_flex::frame::FrameEncoder<W> {
+    fn finish(self: Box<Self>) -> std::io::Result<()> {
+        (*self).finish().map_err(std::io::Error::other)?.flush()
+    }
+}
+
+#[cfg(feature = "brotli")]
+impl<W: Write> FinishWrite for brotli::CompressorWriter<W> {
+    fn finish(mut self: Box<Self>) -> std::io::Result<()> {
+        // Surfaces errors of writing the compressed data, which `into_inner` ignores.
+        self.flush()?;
+        self.into_inner().flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use strum::VariantArray as _;
+
+    use super::Compression;
+
+    #[test]
+    fn peels_compression_extension() {
+        for compression in Compression::VARIANTS {
+            let path = format!("snapshots/state.json.{}", compression.file_ext());
+            assert_eq!(
+                Compression::peel(Path::new(&path)),
+                (Some(*compression), Path::new("state.json"))
+            );
+        }
+        let path = Path::new("snapshots/state.json");
+        assert_eq!(Compression::peel(path), (None, path));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn files_round_trip_through_every_compression() -> color_eyre::Result<()> {
+        let serializable = vec![(1_u8, "state".to_owned()); 64];
+        for compression in Compression::VARIANTS {
+            let path = std::env::temp_dir().join(format!(
+                "sedes-files-round-trip-through-every-compression.json.{}",
+                compression.file_ext()
+            ));
+            crate::serialize_to_file(&path, "W", &serializable)?;
+            let compressed = std::fs::read(&path)?;
+            assert!(compressed.len() < serde_json::to_vec(&serializable)?.len());
+            let deserialized: Vec<(u8, String)> = crate::deserialize_from_file(&path)?;
+            assert_eq!(deserialized, serializable, "{compression}");
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "jsonl")]
+    #[test]
+    fn appended_files_read_back_whole() -> color_eyre::Result<()> {
+        for compression in Compression::VARIANTS {
+            let path = std::env::temp_dir().join(format!(
+                "sedes-appended-files-read-back-whole.jsonl.{}",
+                compression.file_ext()
+            ));
+            crate::serialize_to_file(&path, "W", &1)?;
+            let appended = crate::serialize_to_file(&path, "A", &2);
+            if !compression.concatenates() {
+                assert!(appended.is_err(), "{compression}");
+                continue;
+            }
+            appended?;
+            let values = crate::make_deserializer(
+                std::fs::File::open(&path)?,
+                (crate::DeserializationFormat::JsonLines, *compression),
+            )?
+            .into_values::<u8>()
+            .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(values, [1, 2], "{compression}");
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "json", feature = "compression"))]
+    #[test]
+    fn rejects_data_after_compressed_stream() -> color_eyre::Result<()> {
+        for &compression in Compression::VARIANTS {
+            let mut sink = Vec::<u8>::new();
+            crate::serialize_magically(
+                &mut sink,
+                (crate::SerializationFormat::PrettyJson, compression),
+                &1,
+            )?;
+            sink.extend_from_slice(b"garbage");
+            let deserialized = crate::deserialize_magically::<_, _, u8>(
+                sink.as_slice(),
+                (crate::DeserializationFormat::Json, compression),
+            );
+            assert!(deserialized.is_err(), "{compression}");
+        }
+        Ok(())
+    }
+
+    #[cfg(all(feature = "cbor", feature = "zstd"))]
+    #[test]
+    fn overwritten_file_holds_only_new_stream() -> color_eyre::Result<()> {
+        let path =
+            std::env::temp_dir().join("sedes-overwritten-file-holds-only-new-stream.cbor.zst");
+        crate::serialize_to_file(
+            &path,
+            "W",
+            &"a much longer value than the next one".repeat(8),
+        )?;
+        crate::serialize_to_file(&path, "W", "short")?;
+        let deserialized: String = crate::deserialize_from_file(&path)?;
+        assert_eq!(deserialized, "short");
+        Ok(())
+    }
+}
@@ -26,7 +26,7 @@ pub trait SedeFormat {
 
 impl<D> SedeFormat for D
 where
-    D: Clone + Copy + From<DeserializationFormat> + for<'a> From<&'a DeserializationFormat>,
+    D: Clone + Copy + TryFrom<DeserializationFormat>,
     DeserializationFormat: From<D> + for<'a> From<&'a D>,
 {
     fn from_file_ext_os(file_extension: &OsStr) -> Option<Self> {
@@ -35,14 +35,13 @@ where
     }
     fn from_file_ext(file_extension: &str) -> Option<Self> {
         let des_fmt = FROM_FILE_EXT.get(file_extension).copied()?;
-        Some(des_fmt.into())
+        des_fmt.try_into().ok()
     }
 
     fn file_exts(&self) -> impl IntoIterator<Item = &'static str> {
         let favoured_ext = self.favoured_file_ext();
         let alt_exts = self.alt_file_exts();
-        let all_exts = std::iter::once(favoured_ext).chain(alt_exts);
-        all_exts
+        std::iter::once(favoured_ext).chain(alt_exts)
     }
 
     fn favoured_file_ext(&self) -> &'static str {
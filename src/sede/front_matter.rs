@@ -0,0 +1,53 @@
+//! Front matter, i.e. a block of metadata opening a Markdown file.
+
+/// The format of front matter, told by the lines delimiting it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Delimiter {
+    /// `---`
+    Yaml,
+    /// `+++`
+    Toml,
+}
+
+impl Delimiter {
+    pub fn line(self) -> &'static str {
+        match self {
+            Self::Yaml => "---",
+            Self::Toml => "+++",
+        }
+    }
+}
+
+/// A Markdown document split into its front matter and its body.
+pub struct Split<'d> {
+    pub delimiter: Delimiter,
+    pub front_matter: &'d str,
+    pub body: &'d str,
+}
+
+/// Splits the document, unless it doesn't open with front matter.
+pub fn split(document: &str) -> Option<Split<'_>> {
+    let document = document.strip_prefix('\u{feff}').unwrap_or(document);
+    let (first_line, rest) = document.split_once('\n')?;
+    let delimiter = [Delimiter::Yaml, Delimiter::Toml]
+        .into_iter()
+        .find(|delimiter| first_line.trim_end() == delimiter.line())?;
+
+    let mut front_matter_len = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == delimiter.line() {
+            return Some(Split {
+                delimiter,
+                front_matter: &rest[..front_matter_len],
+                body: &rest[front_matter_len + line.len()..],
+            });
+        }
+        front_matter_len += line.len();
+    }
+    None
+}
+
+/// The document without its front matter, if any.
+pub fn body(document: &str) -> &str {
+    split(document).map_or(document, |split| split.body)
+}
@@ -1,6 +1,40 @@
 pub trait Something {}
 impl<T> Something for T {}
 
+/// Explains which values `application/x-www-form-urlencoded` bodies can hold.
+#[cfg(feature = "urlencoded")]
+pub const URL_ENCODED_NESTING_LIMITS: &str = "URL-encoded forms hold only a flat struct or map \
+    (or a sequence of key-value pairs) of primitive values, optionally wrapped in `Option`; \
+    nested structs, maps and sequences cannot be represented";
+
+/// The extension of the file, or the name of a dotfile (e.g. `env` for `.env`) that has none.
+pub fn file_ext(path: &std::path::Path) -> Option<&std::ffi::OsStr> {
+    path.extension().or_else(|| {
+        let file_name = path.file_name()?.to_str()?;
+        file_name.strip_prefix('.').map(std::ffi::OsStr::new)
+    })
+}
+
+/// Reads the whole remaining input of a deserializer's reader into a [`String`].
+#[allow(dead_code)]
+pub fn read_document(mut reader: impl std::io::Read) -> Result<String, erased_serde::Error> {
+    let mut document = String::new();
+    reader
+        .read_to_string(&mut document)
+        .map_err(serde::de::Error::custom)?;
+    Ok(document)
+}
+
+/// Reads the whole remaining input of a deserializer's reader into a byte buffer.
+#[allow(dead_code)]
+pub fn read_bytes(mut reader: impl std::io::Read) -> Result<Vec<u8>, erased_serde::Error> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(serde::de::Error::custom)?;
+    Ok(bytes)
+}
+
 #[allow(unused_imports)]
 pub use rc_rf::{RcRfDynWriter, RcRfWriter};
 pub mod rc_rf {
@@ -21,6 +55,15 @@ pub mod rc_rf {
         }
     }
 
+    impl<W> RcRfWriter<W> {
+        /// Takes the writer out, unless it's still shared.
+        pub fn try_into_inner(self) -> Result<W, Self> {
+            Rc::try_unwrap(self.0)
+                .map(RefCell::into_inner)
+                .map_err(Self)
+        }
+    }
+
     impl<W> From<W> for RcRfWriter<W> {
         fn from(value: W) -> Self {
             RefCell::new(value).into()
@@ -58,3 +101,78 @@ pub mod rc_rf {
         }
     }
 }
+
+#[allow(unused_imports)]
+pub use hijack::consume_via_typed_api;
+/// Lets formats exposing only typed deserialization functions (e.g. `from_value::<T>`)
+/// feed their (otherwise private) deserializer to an erased consumer.
+pub mod hijack {
+    use std::{cell::Cell, ptr::NonNull};
+
+    use crate::de::magical::ErasedConsumer;
+
+    thread_local! {
+        static CONSUMER: Cell<Option<NonNull<ErasedConsumer<'static>>>> = const { Cell::new(None) };
+    }
+
+    /// Stands in for the deserialized type, handing its deserializer to the registered consumer.
+    pub struct Hijacked(());
+
+    impl<'de> serde::Deserialize<'de> for Hijacked {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut consumer = CONSUMER
+                .take()
+                .expect("`Hijacked` should only be deserialized by `consume_via_typed_api`");
+            // SAFETY: the consumer is registered only for the duration of `consume_via_typed_api`,
+            // which outlives this call, and is taken out, so it is not aliased.
+            let consume = unsafe { consumer.as_mut() };
+            consume(&mut <dyn erased_serde::Deserializer>::erase(deserializer))
+                .map_err(serde::de::Error::custom)?;
+            Ok(Hijacked(()))
+        }
+    }
+
+    /// Calls `deserialize_typed`, which should deserialize a [`Hijacked`] value,
+    /// so that `consume` receives the deserializer used under the hood.
+    pub fn consume_via_typed_api<E: std::fmt::Display>(
+        consume: &mut ErasedConsumer<'_>,
+        deserialize_typed: impl FnOnce() -> Result<Hijacked, E>,
+    ) -> Result<(), erased_serde::Error> {
+        let consumer: NonNull<ErasedConsumer<'_>> = NonNull::from(consume);
+        // SAFETY: the pointer outlives its real lifetime only in `CONSUMER`, where it stays no
+        // longer than `_registration`, which is dropped before `consume` is released by returning,
+        // even when unwinding (e.g. from a panic caught by brute force deserialization).
+        // `consume` is uniquely borrowed meanwhile, and the pointer is dereferenced only by
+        // `Hijacked`, which takes it out first, so it's never aliased.
+        let consumer: NonNull<ErasedConsumer<'static>> = unsafe { std::mem::transmute(consumer) };
+        let _registration = Registration(CONSUMER.replace(Some(consumer)));
+        deserialize_typed()
+            .map(|Hijacked(())| ())
+            .map_err(serde::de::Error::custom)
+    }
+
+    /// Restores the previously registered consumer, if any, once dropped.
+    struct Registration(Option<NonNull<ErasedConsumer<'static>>>);
+
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            CONSUMER.set(self.0.take());
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::{CONSUMER, Hijacked};
+
+        #[test]
+        fn unregisters_consumer_on_panic() {
+            let panicked = std::panic::catch_unwind(|| {
+                super::consume_via_typed_api(&mut |_| Ok(()), || -> Result<Hijacked, String> {
+                    panic!("the typed API panicked")
+                })
+            });
+            assert!(panicked.is_err());
+            assert!(CONSUMER.get().is_none());
+        }
+    }
+}